@@ -0,0 +1,206 @@
+use std::time::SystemTime;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tide::{Body, Request, Response};
+
+use crate::{checks::upgrade_pending::LabeledStatus, providers::VersionInfo, State};
+
+#[derive(Debug, Serialize)]
+struct ProviderReport<'a> {
+    name: &'a str,
+    versions: &'a [VersionInfo],
+    /// The error of the last fetch, if it failed.
+    error: Option<String>,
+    cached_at: Option<DateTime<Utc>>,
+    cache_age_seconds: Option<u64>,
+    cache_expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+struct ReleasesReport<'a> {
+    providers: Vec<ProviderReport<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct CheckReport<'a> {
+    name: &'a str,
+    current: &'a str,
+    latest: &'a str,
+    results: Vec<LabeledStatus<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChecksReport<'a> {
+    checks: Vec<CheckReport<'a>>,
+}
+
+/// Returns the versions returned by each provider with the error of the last
+/// fetch and the age of the cached versions.
+pub async fn releases(req: Request<State>) -> tide::Result {
+    let state = req.state();
+    let releases = state.fetch().await;
+    let errors = state.errors.lock().unwrap().clone();
+    let cache = state.cache.lock().unwrap();
+    let now = SystemTime::now();
+
+    let report = ReleasesReport {
+        providers: state
+            .config
+            .providers
+            .iter()
+            .map(|provider| {
+                let name = provider.name();
+                let cached_at = cache.inserted_at(name).copied();
+                ProviderReport {
+                    name,
+                    versions: releases.get(name).map_or(&[], Vec::as_slice),
+                    error: errors.get(name).cloned(),
+                    cached_at: cached_at.map(DateTime::from),
+                    cache_age_seconds: cached_at.map(|cached_at| {
+                        now.duration_since(cached_at).unwrap_or_default().as_secs()
+                    }),
+                    cache_expires_at: cache.expires_at(name).copied().map(DateTime::from),
+                }
+            })
+            .collect(),
+    };
+    Ok(Response::builder(200)
+        .body(Body::from_json(&report)?)
+        .build())
+}
+
+/// Returns the results of all upgrade pending checks.
+pub async fn checks(req: Request<State>) -> tide::Result {
+    let state = req.state();
+    let releases = state.fetch().await;
+
+    let report = ChecksReport {
+        checks: state
+            .config
+            .upgrade_pending_checks
+            .iter()
+            .map(|check| CheckReport {
+                name: &check.name,
+                current: &check.current,
+                latest: &check.latest,
+                results: check.check(&releases),
+            })
+            .collect(),
+    };
+    Ok(Response::builder(200)
+        .body(Body::from_json(&report)?)
+        .build())
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::{Client, Url};
+    use serde_json::{json, Value};
+    use tide::http::{Method, Request, Response};
+
+    use crate::{create_app, Config};
+
+    fn config() -> Config {
+        serde_yaml::from_str(
+            r#"
+            providers:
+              - name: current_app_release
+                provider: static
+                versions:
+                  - version: 1.0.0
+                    labels:
+                      team: a
+              - name: latest_app_release
+                provider: static
+                cache_seconds: 60
+                versions:
+                  - version: 1.1.0
+              - name: unreachable
+                provider: prometheus
+                api_url: http://127.0.0.1:1/
+                query: build_info
+                label: version
+            upgrade_pending_checks:
+              - name: app
+            "#,
+        )
+        .unwrap()
+    }
+
+    async fn get(app: &tide::Server<crate::State>, path: &str) -> Value {
+        let url = Url::parse("http://localhost").unwrap().join(path).unwrap();
+        let mut response: Response = app.respond(Request::new(Method::Get, url)).await.unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            response.content_type().unwrap().essence(),
+            "application/json"
+        );
+        response.body_json().await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_releases_endpoint() {
+        let app = create_app(config(), Client::new());
+        let report = get(&app, "/api/v1/releases").await;
+
+        let providers = report["providers"].as_array().unwrap();
+        assert_eq!(providers.len(), 3);
+        assert_eq!(providers[0]["name"], "current_app_release");
+        assert_eq!(
+            providers[0]["versions"],
+            json!([{
+                "version": "1.0.0",
+                "labels": {"team": "a"},
+                "metadata": {"published_at": null, "url": null, "prerelease": null},
+            }])
+        );
+        assert_eq!(providers[0]["error"], Value::Null);
+        assert!(providers[0]["cached_at"].is_string());
+        assert_eq!(providers[1]["cache_age_seconds"], 0);
+        assert!(providers[1]["cache_expires_at"].is_string());
+        assert_eq!(providers[2]["versions"], json!([]));
+        assert!(providers[2]["error"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_checks_endpoint() {
+        let app = create_app(config(), Client::new());
+        let report = get(&app, "/api/v1/checks").await;
+
+        assert_eq!(
+            report,
+            json!({
+                "checks": [{
+                    "name": "app",
+                    "current": "current_app_release",
+                    "latest": "latest_app_release",
+                    "results": [{
+                        "labels": {"team": "a"},
+                        "current_version": "1.0.0",
+                        "status": "upgrade-available",
+                        "severity": "minor",
+                        "latest_version": "1.1.0",
+                        "releases_behind": null,
+                        "latest_published_at": null,
+                        "upgrade_pending_since": null,
+                        "unconstrained_latest_version": null,
+                    }],
+                }],
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_requests_do_not_count_cache_lookups() {
+        let app = create_app(config(), Client::new());
+        get(&app, "/api/v1/releases").await;
+        get(&app, "/api/v1/checks").await;
+
+        let url = Url::parse("http://localhost/metrics").unwrap();
+        let mut response: Response = app.respond(Request::new(Method::Get, url)).await.unwrap();
+        let body = response.body_string().await.unwrap();
+        assert!(body.contains("release_cache_hits_total{provider=\"latest_app_release\"} 1"));
+        assert!(!body.contains("release_cache_misses_total{provider=\"latest_app_release\"}"));
+    }
+}
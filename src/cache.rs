@@ -43,13 +43,19 @@ impl<T: Eq + Ord> Ord for MinOrder<T> {
     }
 }
 
+struct CacheEntry<T> {
+    releases: Vec<VersionInfo>,
+    inserted_at: T,
+    expires_at: T,
+}
+
 pub struct ReleaseCache<T: Add<Duration> + Ord, C: Clock<T>> {
-    cache: HashMap<String, Vec<VersionInfo>>,
+    cache: HashMap<String, CacheEntry<T>>,
     expiry: BinaryHeap<(MinOrder<T>, String)>,
     clock: C,
 }
 
-impl<T: Add<Duration, Output = T> + std::fmt::Debug + Ord, C: Clock<T>> ReleaseCache<T, C> {
+impl<T: Add<Duration, Output = T> + std::fmt::Debug + Clone + Ord, C: Clock<T>> ReleaseCache<T, C> {
     pub fn new(clock: C) -> Self {
         Self {
             cache: HashMap::new(),
@@ -63,13 +69,36 @@ impl<T: Add<Duration, Output = T> + std::fmt::Debug + Ord, C: Clock<T>> ReleaseC
     }
 
     pub fn get(&self, key: &str) -> Option<&Vec<VersionInfo>> {
-        self.cache.get(key)
+        self.cache.get(key).map(|entry| &entry.releases)
+    }
+
+    pub fn inserted_at(&self, key: &str) -> Option<&T> {
+        self.cache.get(key).map(|entry| &entry.inserted_at)
+    }
+
+    pub fn expires_at(&self, key: &str) -> Option<&T> {
+        self.cache.get(key).map(|entry| &entry.expires_at)
+    }
+
+    /// The keys of all cached entries with their expiry time.
+    pub fn expiries(&self) -> impl Iterator<Item = (&str, &T)> {
+        self.cache
+            .iter()
+            .map(|(key, entry)| (key.as_str(), &entry.expires_at))
     }
 
     pub fn insert(&mut self, key: String, releases: Vec<VersionInfo>, cache_duration: Duration) {
-        self.cache.insert(key.clone(), releases);
-        self.expiry
-            .push((MinOrder(self.clock.now() + cache_duration), key));
+        let inserted_at = self.clock.now();
+        let expires_at = inserted_at.clone() + cache_duration;
+        self.cache.insert(
+            key.clone(),
+            CacheEntry {
+                releases,
+                inserted_at,
+                expires_at: expires_at.clone(),
+            },
+        );
+        self.expiry.push((MinOrder(expires_at), key));
     }
 
     pub fn expire(&mut self) {
@@ -134,6 +163,12 @@ mod tests {
         cache.insert("key".into(), vec![], Duration::from_secs(10));
         assert!(cache.contains_key("key"));
         assert_eq!(cache.get("key"), Some(&vec![]));
+        assert_eq!(cache.inserted_at("key"), Some(&Timestamp(0)));
+        assert_eq!(cache.expires_at("key"), Some(&Timestamp(10)));
+        assert_eq!(
+            cache.expiries().collect::<Vec<_>>(),
+            vec![("key", &Timestamp(10))]
+        );
 
         cache.expire();
         assert!(cache.contains_key("key"));
@@ -143,5 +178,6 @@ mod tests {
         cache.expire();
         assert!(!cache.contains_key("key"));
         assert!(cache.get("key").is_none());
+        assert_eq!(cache.expiries().count(), 0);
     }
 }
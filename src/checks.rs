@@ -1 +1,3 @@
+pub mod error;
 pub mod upgrade_pending;
+pub mod version_constraint;
@@ -0,0 +1,15 @@
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to read aliases file {path}: {source}")]
+    AliasesFileRead {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+    #[error("failed to parse aliases file {path}: {source}")]
+    AliasesFileParse {
+        path: std::path::PathBuf,
+        source: serde_yaml::Error,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
@@ -1,14 +1,35 @@
-use std::collections::HashMap;
+use std::{borrow::Cow, cmp::Ordering, collections::HashMap, fs::File, path::PathBuf};
 
-use serde::Deserialize;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
-use crate::providers::VersionInfo;
+use super::{
+    error::{self, Error},
+    version_constraint::VersionConstraint,
+};
+use crate::{
+    normalization::Normalization,
+    providers::VersionInfo,
+    version_scheme::{Severity, VersionScheme},
+};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct UpgradePendingCheck {
     pub name: String,
     pub current: String,
     pub latest: String,
+    /// Defaults to the version scheme of the `current` provider, see
+    /// [`UpgradePendingCheck::scheme`].
+    pub version_scheme: Option<VersionScheme>,
+    /// Only versions of the `latest` provider satisfying the constraint are
+    /// considered.
+    pub constraint: Option<VersionConstraint>,
+    /// Applied to versions before they are compared.
+    pub normalize: Normalization,
+    /// Maps versions to equivalent versions before they are normalized.
+    pub aliases: HashMap<String, String>,
+    /// File with additional aliases, see [`UpgradePendingCheck::load_aliases_file`].
+    pub aliases_file: Option<PathBuf>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -16,6 +37,13 @@ struct UpgradePendingCheckWithOptionals {
     name: String,
     current: Option<String>,
     latest: Option<String>,
+    version_scheme: Option<VersionScheme>,
+    constraint: Option<VersionConstraint>,
+    #[serde(default)]
+    normalize: Normalization,
+    #[serde(default)]
+    aliases: HashMap<String, String>,
+    aliases_file: Option<PathBuf>,
 }
 
 impl<'de> Deserialize<'de> for UpgradePendingCheck {
@@ -27,27 +55,61 @@ impl<'de> Deserialize<'de> for UpgradePendingCheck {
             name,
             current,
             latest,
+            version_scheme,
+            constraint,
+            normalize,
+            aliases,
+            aliases_file,
         } = UpgradePendingCheckWithOptionals::deserialize(deserializer)?;
         Ok(Self {
             current: current.unwrap_or_else(|| format!("current_{name}_release")),
             latest: latest.unwrap_or_else(|| format!("latest_{name}_release")),
             name,
+            version_scheme,
+            constraint,
+            normalize,
+            aliases,
+            aliases_file,
         })
     }
 }
 
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum CheckStatus {
     UpToDate,
     UpgradeAvailable,
+    AheadOfLatest,
+    Incomparable,
     Unknown,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+impl CheckStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CheckStatus::Unknown => "unknown",
+            CheckStatus::UpToDate => "up-to-date",
+            CheckStatus::UpgradeAvailable => "upgrade-available",
+            CheckStatus::AheadOfLatest => "ahead-of-latest",
+            CheckStatus::Incomparable => "incomparable",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
 pub struct LabeledStatus<'a> {
     pub labels: &'a HashMap<String, String>,
+    pub current_version: Option<&'a str>,
     pub status: CheckStatus,
+    pub severity: Option<Severity>,
     pub latest_version: Option<&'a str>,
+    pub releases_behind: Option<u64>,
+    pub latest_published_at: Option<DateTime<Utc>>,
+    /// Publication time of the oldest release newer than the current version.
+    pub upgrade_pending_since: Option<DateTime<Utc>>,
+    /// The highest matching version ignoring the constraint. Only set if the
+    /// check has a constraint.
+    pub unconstrained_latest_version: Option<&'a str>,
 }
 
 impl UpgradePendingCheck {
@@ -62,42 +124,229 @@ impl UpgradePendingCheck {
             (Some(current), latest) => current
                 .iter()
                 .map(|v| {
-                    let latest_version = Self::latest_version(v, latest.map(Vec::as_ref));
+                    let all_candidates = Self::matching_releases(v, latest.map(Vec::as_ref));
+                    let candidates = self.constrained(&all_candidates);
+                    let latest_release = self.highest_release(&candidates);
+                    let latest_version = latest_release.and_then(|r| r.version.as_deref());
+                    let status = match (&v.version, latest_version) {
+                        (None, _) => CheckStatus::Unknown,
+                        (_, None) => CheckStatus::Unknown,
+                        (Some(current_version), Some(latest_version)) => {
+                            self.compare(current_version, latest_version)
+                        }
+                    };
                     LabeledStatus {
                         labels: &v.labels,
-                        status: match (&v.version, latest_version) {
-                            (None, _) => CheckStatus::Unknown,
-                            (_, None) => CheckStatus::Unknown,
-                            (Some(current_version), Some(latest_version)) => {
-                                if current_version == latest_version {
-                                    CheckStatus::UpToDate
-                                } else {
-                                    CheckStatus::UpgradeAvailable
-                                }
-                            }
+                        current_version: v.version.as_deref(),
+                        status,
+                        severity: match (status, v.version.as_deref(), latest_version) {
+                            (
+                                CheckStatus::UpgradeAvailable,
+                                Some(current_version),
+                                Some(latest_version),
+                            ) => self.severity(current_version, latest_version),
+                            _ => None,
                         },
                         latest_version,
+                        releases_behind: v.version.as_deref().zip(latest_version).and_then(
+                            |(current_version, latest_version)| {
+                                self.releases_behind(current_version, latest_version, &candidates)
+                            },
+                        ),
+                        latest_published_at: latest_release.and_then(|r| r.metadata.published_at),
+                        upgrade_pending_since: match (status, v.version.as_deref(), latest_version)
+                        {
+                            (
+                                CheckStatus::UpgradeAvailable,
+                                Some(current_version),
+                                Some(latest_version),
+                            ) => self.upgrade_pending_since(
+                                current_version,
+                                latest_version,
+                                &candidates,
+                            ),
+                            _ => None,
+                        },
+                        unconstrained_latest_version: self.constraint.as_ref().and_then(|_| {
+                            self.highest_release(&all_candidates)
+                                .and_then(|r| r.version.as_deref())
+                        }),
                     }
                 })
                 .collect(),
         }
     }
 
-    fn latest_version<'a>(
+    /// The version scheme used to compare versions. Falls back to the default
+    /// scheme if neither configured nor resolved from the `current` provider.
+    pub fn scheme(&self) -> VersionScheme {
+        self.version_scheme.unwrap_or_default()
+    }
+
+    /// Loads the aliases from the `aliases_file`, if any. Aliases configured
+    /// directly in the check take precedence.
+    pub fn load_aliases_file(&mut self) -> error::Result<()> {
+        let Some(path) = &self.aliases_file else {
+            return Ok(());
+        };
+        let file = File::open(path).map_err(|source| Error::AliasesFileRead {
+            path: path.clone(),
+            source,
+        })?;
+        let aliases: HashMap<String, String> =
+            serde_yaml::from_reader(file).map_err(|source| Error::AliasesFileParse {
+                path: path.clone(),
+                source,
+            })?;
+        for (alias, version) in aliases {
+            self.aliases.entry(alias).or_insert(version);
+        }
+        Ok(())
+    }
+
+    /// Resolves aliases and normalizes the version for comparison.
+    fn canonical<'s>(&'s self, version: &'s str) -> Cow<'s, str> {
+        let version = self.aliases.get(version).map_or(version, String::as_str);
+        self.normalize.apply(version)
+    }
+
+    /// Compares two versions according to the scheme after resolving aliases
+    /// and normalizing them.
+    fn compare_versions(&self, a: &str, b: &str) -> Option<Ordering> {
+        self.scheme()
+            .compare(&self.canonical(a), &self.canonical(b))
+    }
+
+    fn severity(&self, current_version: &str, latest_version: &str) -> Option<Severity> {
+        self.scheme().severity(
+            &self.canonical(current_version),
+            &self.canonical(latest_version),
+        )
+    }
+
+    fn compare(&self, current_version: &str, latest_version: &str) -> CheckStatus {
+        if self.canonical(current_version) == self.canonical(latest_version) {
+            return CheckStatus::UpToDate;
+        }
+        match self.compare_versions(current_version, latest_version) {
+            Some(Ordering::Less) => CheckStatus::UpgradeAvailable,
+            Some(Ordering::Equal) => CheckStatus::UpToDate,
+            Some(Ordering::Greater) => CheckStatus::AheadOfLatest,
+            None => CheckStatus::Incomparable,
+        }
+    }
+
+    /// Releases of the `latest` provider whose labels are all present with the
+    /// same value in the `current` release.
+    fn matching_releases<'a>(
         current: &VersionInfo,
         latest: Option<&'a [VersionInfo]>,
-    ) -> Option<&'a str> {
-        match latest {
-            None => None,
-            Some(latest) => {
-                let matching_release = latest.iter().find(|v| {
-                    v.labels
-                        .iter()
-                        .all(|(label, value)| current.labels.get(label) == Some(value))
-                });
-                matching_release.and_then(|r| r.version.as_deref())
+    ) -> Vec<&'a VersionInfo> {
+        latest
+            .unwrap_or_default()
+            .iter()
+            .filter(|v| {
+                v.labels
+                    .iter()
+                    .all(|(label, value)| current.labels.get(label) == Some(value))
+            })
+            .collect()
+    }
+
+    /// The releases satisfying the constraint, if any.
+    fn constrained<'a>(&self, releases: &[&'a VersionInfo]) -> Vec<&'a VersionInfo> {
+        releases
+            .iter()
+            .copied()
+            .filter(|r| match (&self.constraint, r.version.as_deref()) {
+                (None, _) => true,
+                (Some(constraint), Some(version)) => {
+                    constraint.matches(&self.canonical(version), self.scheme())
+                }
+                (Some(_), None) => false,
+            })
+            .collect()
+    }
+
+    /// The release with the highest version. If versions cannot be ordered,
+    /// the first one is preferred.
+    fn highest_release<'a>(&self, releases: &[&'a VersionInfo]) -> Option<&'a VersionInfo> {
+        releases
+            .iter()
+            .copied()
+            .filter(|r| r.version.is_some())
+            .reduce(|highest, release| {
+                match release
+                    .version
+                    .as_deref()
+                    .zip(highest.version.as_deref())
+                    .and_then(|(version, highest)| self.compare_versions(version, highest))
+                {
+                    Some(Ordering::Greater) => release,
+                    _ => highest,
+                }
+            })
+    }
+
+    fn is_same_version(&self, a: &str, b: &str) -> bool {
+        self.canonical(a) == self.canonical(b)
+            || self.compare_versions(a, b) == Some(Ordering::Equal)
+    }
+
+    /// Whether the version is newer than the current version, but not newer
+    /// than the latest version.
+    fn is_pending(&self, version: &str, current_version: &str, latest_version: &str) -> bool {
+        self.compare_versions(version, current_version) == Some(Ordering::Greater)
+            && self.compare_versions(version, latest_version) != Some(Ordering::Greater)
+    }
+
+    /// The earliest publication time of the releases newer than the current
+    /// version up to and including the latest version.
+    fn upgrade_pending_since(
+        &self,
+        current_version: &str,
+        latest_version: &str,
+        releases: &[&VersionInfo],
+    ) -> Option<DateTime<Utc>> {
+        releases
+            .iter()
+            .filter(|r| {
+                r.version
+                    .as_deref()
+                    .is_some_and(|v| self.is_pending(v, current_version, latest_version))
+            })
+            .filter_map(|r| r.metadata.published_at)
+            .min()
+    }
+
+    /// Counts the releases newer than the current version up to and including
+    /// the latest version. Only known if the current version is among the
+    /// releases, as otherwise the release list might be incomplete.
+    fn releases_behind(
+        &self,
+        current_version: &str,
+        latest_version: &str,
+        releases: &[&VersionInfo],
+    ) -> Option<u64> {
+        // Versions comparing equal, e.g. `1.0` and `1.0.0`, count as one release.
+        let mut versions: Vec<&str> = vec![];
+        for version in releases.iter().filter_map(|r| r.version.as_deref()) {
+            if !versions.iter().any(|v| self.is_same_version(v, version)) {
+                versions.push(version);
             }
         }
+        if !versions
+            .iter()
+            .any(|v| self.is_same_version(v, current_version))
+        {
+            return None;
+        }
+        Some(
+            versions
+                .iter()
+                .filter(|v| self.is_pending(v, current_version, latest_version))
+                .count() as u64,
+        )
     }
 }
 
@@ -109,18 +358,38 @@ mod tests {
 
     use crate::{
         checks::upgrade_pending::{CheckStatus, LabeledStatus},
-        providers::VersionInfo,
+        normalization::Normalization,
+        providers::{ReleaseMetadata, VersionInfo},
+        test_config::TempFile,
+        version_scheme::{Severity, VersionScheme},
     };
 
-    use super::UpgradePendingCheck;
+    use super::{UpgradePendingCheck, VersionConstraint};
 
-    #[test]
-    fn test_upgrade_pending_check() {
-        let check = UpgradePendingCheck {
+    fn check() -> UpgradePendingCheck {
+        UpgradePendingCheck {
             name: "name".into(),
             current: "current".into(),
             latest: "latest".into(),
-        };
+            version_scheme: Some(VersionScheme::Semver),
+            constraint: None,
+            normalize: Normalization::default(),
+            aliases: HashMap::new(),
+            aliases_file: None,
+        }
+    }
+
+    fn release(version: &str) -> VersionInfo {
+        VersionInfo {
+            labels: HashMap::new(),
+            version: Some(version.into()),
+            metadata: ReleaseMetadata::default(),
+        }
+    }
+
+    #[test]
+    fn test_upgrade_pending_check() {
+        let check = check();
         let mut releases: HashMap<String, Vec<VersionInfo>> = HashMap::new();
         releases.insert(
             "current".into(),
@@ -130,29 +399,34 @@ mod tests {
                         ("stream".into(), "stable".into()),
                         ("instance".into(), "production-01".into()),
                     ]),
-                    version: Some("v-current".into()),
+                    version: Some("1.0.0".into()),
+                    metadata: ReleaseMetadata::default(),
                 },
                 VersionInfo {
                     labels: HashMap::from([
                         ("stream".into(), "stable".into()),
                         ("instance".into(), "production-02".into()),
                     ]),
-                    version: Some("v-current".into()),
+                    version: Some("1.0.0".into()),
+                    metadata: ReleaseMetadata::default(),
                 },
                 VersionInfo {
                     labels: HashMap::from([
                         ("stream".into(), "testing".into()),
                         ("instance".into(), "staging".into()),
                     ]),
-                    version: Some("v-testing-current".into()),
+                    version: Some("1.1.0-rc.1".into()),
+                    metadata: ReleaseMetadata::default(),
                 },
                 VersionInfo {
                     labels: HashMap::from([("stream".into(), "no-latest".into())]),
-                    version: Some("v-no-latest".into()),
+                    version: Some("0.9.0".into()),
+                    metadata: ReleaseMetadata::default(),
                 },
                 VersionInfo {
                     labels: HashMap::from([("stream".into(), "no-match".into())]),
-                    version: Some("v-no-match".into()),
+                    version: Some("0.9.1".into()),
+                    metadata: ReleaseMetadata::default(),
                 },
                 VersionInfo {
                     labels: HashMap::from([
@@ -160,6 +434,23 @@ mod tests {
                         ("instance".into(), "unreachable".into()),
                     ]),
                     version: None,
+                    metadata: ReleaseMetadata::default(),
+                },
+                VersionInfo {
+                    labels: HashMap::from([
+                        ("stream".into(), "stable".into()),
+                        ("instance".into(), "canary".into()),
+                    ]),
+                    version: Some("1.2.0-dev".into()),
+                    metadata: ReleaseMetadata::default(),
+                },
+                VersionInfo {
+                    labels: HashMap::from([
+                        ("stream".into(), "stable".into()),
+                        ("instance".into(), "legacy".into()),
+                    ]),
+                    version: Some("nightly".into()),
+                    metadata: ReleaseMetadata::default(),
                 },
             ],
         );
@@ -168,19 +459,23 @@ mod tests {
             vec![
                 VersionInfo {
                     labels: HashMap::from([("stream".into(), "stable".into())]),
-                    version: Some("v-latest".into()),
+                    version: Some("1.1.0".into()),
+                    metadata: ReleaseMetadata::default(),
                 },
                 VersionInfo {
                     labels: HashMap::from([("stream".into(), "testing".into())]),
-                    version: Some("v-testing-current".into()),
+                    version: Some("1.1.0-rc.1".into()),
+                    metadata: ReleaseMetadata::default(),
                 },
                 VersionInfo {
                     labels: HashMap::from([("stream".into(), "no-latest".into())]),
                     version: None,
+                    metadata: ReleaseMetadata::default(),
                 },
                 VersionInfo {
                     labels: HashMap::from([("stream".into(), "ignored".into())]),
-                    version: Some("v-ignored".into()),
+                    version: Some("2.0.0".into()),
+                    metadata: ReleaseMetadata::default(),
                 },
             ],
         );
@@ -188,7 +483,8 @@ mod tests {
             "ignored".into(),
             vec![VersionInfo {
                 labels: HashMap::from([("stream".into(), "stable".into())]),
-                version: Some("v-ignored".into()),
+                version: Some("2.0.0".into()),
+                metadata: ReleaseMetadata::default(),
             }],
         );
         let labels_stable_01 = HashMap::from([
@@ -209,52 +505,300 @@ mod tests {
             ("stream".into(), "testing".into()),
             ("instance".into(), "unreachable".into()),
         ]);
+        let labels_canary = HashMap::from([
+            ("stream".into(), "stable".into()),
+            ("instance".into(), "canary".into()),
+        ]);
+        let labels_legacy = HashMap::from([
+            ("stream".into(), "stable".into()),
+            ("instance".into(), "legacy".into()),
+        ]);
         let expected = vec![
             LabeledStatus {
                 labels: &labels_stable_01,
+                current_version: Some("1.0.0"),
                 status: CheckStatus::UpgradeAvailable,
-                latest_version: Some("v-latest"),
+                severity: Some(Severity::Minor),
+                latest_version: Some("1.1.0"),
+                releases_behind: None,
+                latest_published_at: None,
+                upgrade_pending_since: None,
+                unconstrained_latest_version: None,
             },
             LabeledStatus {
                 labels: &labels_stable_02,
+                current_version: Some("1.0.0"),
                 status: CheckStatus::UpgradeAvailable,
-                latest_version: Some("v-latest"),
+                severity: Some(Severity::Minor),
+                latest_version: Some("1.1.0"),
+                releases_behind: None,
+                latest_published_at: None,
+                upgrade_pending_since: None,
+                unconstrained_latest_version: None,
             },
             LabeledStatus {
                 labels: &labels_testing,
+                current_version: Some("1.1.0-rc.1"),
                 status: CheckStatus::UpToDate,
-                latest_version: Some("v-testing-current"),
+                severity: None,
+                latest_version: Some("1.1.0-rc.1"),
+                releases_behind: Some(0),
+                latest_published_at: None,
+                upgrade_pending_since: None,
+                unconstrained_latest_version: None,
             },
             LabeledStatus {
                 labels: &labels_no_latest,
+                current_version: Some("0.9.0"),
                 status: CheckStatus::Unknown,
+                severity: None,
                 latest_version: None,
+                releases_behind: None,
+                latest_published_at: None,
+                upgrade_pending_since: None,
+                unconstrained_latest_version: None,
             },
             LabeledStatus {
                 labels: &labels_no_match,
+                current_version: Some("0.9.1"),
                 status: CheckStatus::Unknown,
+                severity: None,
                 latest_version: None,
+                releases_behind: None,
+                latest_published_at: None,
+                upgrade_pending_since: None,
+                unconstrained_latest_version: None,
             },
             LabeledStatus {
                 labels: &labels_unreachable,
+                current_version: None,
                 status: CheckStatus::Unknown,
-                latest_version: Some("v-testing-current"),
+                severity: None,
+                latest_version: Some("1.1.0-rc.1"),
+                releases_behind: None,
+                latest_published_at: None,
+                upgrade_pending_since: None,
+                unconstrained_latest_version: None,
+            },
+            LabeledStatus {
+                labels: &labels_canary,
+                current_version: Some("1.2.0-dev"),
+                status: CheckStatus::AheadOfLatest,
+                severity: None,
+                latest_version: Some("1.1.0"),
+                releases_behind: None,
+                latest_published_at: None,
+                upgrade_pending_since: None,
+                unconstrained_latest_version: None,
+            },
+            LabeledStatus {
+                labels: &labels_legacy,
+                current_version: Some("nightly"),
+                status: CheckStatus::Incomparable,
+                severity: None,
+                latest_version: Some("1.1.0"),
+                releases_behind: None,
+                latest_published_at: None,
+                upgrade_pending_since: None,
+                unconstrained_latest_version: None,
+            },
+        ];
+        assert_eq!(check.check(&releases), expected);
+    }
+
+    #[test]
+    fn test_upgrade_pending_check_build_metadata() {
+        let check = check();
+        let releases = HashMap::from([
+            ("current".into(), vec![release("1.0.0+a")]),
+            ("latest".into(), vec![release("1.0.0+b")]),
+        ]);
+        let result = &check.check(&releases)[0];
+        assert_eq!(result.status, CheckStatus::UpToDate);
+        assert_eq!(result.severity, None);
+    }
+
+    #[test]
+    fn test_releases_behind_counts_equal_versions_once() {
+        let check = UpgradePendingCheck {
+            version_scheme: Some(VersionScheme::Loose),
+            ..check()
+        };
+        let releases = HashMap::from([
+            ("current".into(), vec![release("0.9")]),
+            (
+                "latest".into(),
+                vec![
+                    release("1.1"),
+                    release("1.0"),
+                    release("1.0.0"),
+                    release("0.9.0"),
+                ],
+            ),
+        ]);
+        assert_eq!(check.check(&releases)[0].releases_behind, Some(2));
+    }
+
+    #[test]
+    fn test_upgrade_pending_check_release_list() {
+        let check = check();
+        let published_release = |version: &str, published_at: &str| VersionInfo {
+            metadata: ReleaseMetadata {
+                published_at: Some(published_at.parse().unwrap()),
+                ..ReleaseMetadata::default()
+            },
+            ..release(version)
+        };
+        let mut releases: HashMap<String, Vec<VersionInfo>> = HashMap::new();
+        releases.insert(
+            "current".into(),
+            vec![
+                published_release("1.0.0", "2024-01-01T00:00:00Z"),
+                published_release("1.2.0", "2024-01-20T00:00:00Z"),
+                published_release("0.9.0", "2023-12-01T00:00:00Z"),
+            ],
+        );
+        releases.insert(
+            "latest".into(),
+            vec![
+                published_release("1.1.0", "2024-01-10T00:00:00Z"),
+                published_release("1.2.0", "2024-01-20T00:00:00Z"),
+                published_release("1.0.0", "2024-01-01T00:00:00Z"),
+                published_release("1.0.1", "2024-01-05T00:00:00Z"),
+                published_release("1.0.1", "2024-01-05T00:00:00Z"),
+            ],
+        );
+        let labels = HashMap::new();
+        let expected = vec![
+            LabeledStatus {
+                labels: &labels,
+                current_version: Some("1.0.0"),
+                status: CheckStatus::UpgradeAvailable,
+                severity: Some(Severity::Minor),
+                latest_version: Some("1.2.0"),
+                releases_behind: Some(3),
+                latest_published_at: Some("2024-01-20T00:00:00Z".parse().unwrap()),
+                upgrade_pending_since: Some("2024-01-05T00:00:00Z".parse().unwrap()),
+                unconstrained_latest_version: None,
+            },
+            LabeledStatus {
+                labels: &labels,
+                current_version: Some("1.2.0"),
+                status: CheckStatus::UpToDate,
+                severity: None,
+                latest_version: Some("1.2.0"),
+                releases_behind: Some(0),
+                latest_published_at: Some("2024-01-20T00:00:00Z".parse().unwrap()),
+                upgrade_pending_since: None,
+                unconstrained_latest_version: None,
+            },
+            LabeledStatus {
+                labels: &labels,
+                current_version: Some("0.9.0"),
+                status: CheckStatus::UpgradeAvailable,
+                severity: Some(Severity::Major),
+                latest_version: Some("1.2.0"),
+                releases_behind: None,
+                latest_published_at: Some("2024-01-20T00:00:00Z".parse().unwrap()),
+                upgrade_pending_since: Some("2024-01-01T00:00:00Z".parse().unwrap()),
+                unconstrained_latest_version: None,
             },
         ];
         assert_eq!(check.check(&releases), expected);
     }
 
+    #[test]
+    fn test_upgrade_pending_check_constraint() {
+        let check = UpgradePendingCheck {
+            version_scheme: Some(VersionScheme::Loose),
+            constraint: VersionConstraint::parse("15.*"),
+            ..check()
+        };
+        let mut releases: HashMap<String, Vec<VersionInfo>> = HashMap::new();
+        releases.insert("current".into(), vec![release("15.3")]);
+        releases.insert(
+            "latest".into(),
+            vec![
+                release("17.0"),
+                release("16.4"),
+                release("15.5"),
+                release("15.4"),
+                release("15.3"),
+            ],
+        );
+        let labels = HashMap::new();
+        assert_eq!(
+            check.check(&releases),
+            vec![LabeledStatus {
+                labels: &labels,
+                current_version: Some("15.3"),
+                status: CheckStatus::UpgradeAvailable,
+                severity: Some(Severity::Minor),
+                latest_version: Some("15.5"),
+                releases_behind: Some(2),
+                latest_published_at: None,
+                upgrade_pending_since: None,
+                unconstrained_latest_version: Some("17.0"),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_upgrade_pending_check_normalize() {
+        let mut check = check();
+        let mut releases: HashMap<String, Vec<VersionInfo>> = HashMap::new();
+        releases.insert("current".into(), vec![release("1.2")]);
+        releases.insert("latest".into(), vec![release("1.2.0")]);
+        let statuses = |check: &UpgradePendingCheck| -> Vec<CheckStatus> {
+            check.check(&releases).iter().map(|s| s.status).collect()
+        };
+
+        assert_eq!(statuses(&check), vec![CheckStatus::Incomparable]);
+        check.normalize = serde_yaml::from_str("[pad_release]").unwrap();
+        assert_eq!(statuses(&check), vec![CheckStatus::UpToDate]);
+    }
+
+    #[test]
+    fn test_upgrade_pending_check_aliases() {
+        let aliases_file = TempFile::new("'2024.2': 24.2.0\n'2024.1': ignored\n");
+        let mut check = UpgradePendingCheck {
+            aliases: HashMap::from([("2024.1".into(), "24.1.0".into())]),
+            aliases_file: Some(aliases_file.path().into()),
+            ..check()
+        };
+        check.load_aliases_file().unwrap();
+        assert_eq!(check.aliases["2024.1"], "24.1.0");
+        assert_eq!(check.aliases["2024.2"], "24.2.0");
+
+        let mut releases: HashMap<String, Vec<VersionInfo>> = HashMap::new();
+        releases.insert("current".into(), vec![release("24.1.0"), release("2024.2")]);
+        releases.insert("latest".into(), vec![release("2024.1")]);
+        assert_eq!(
+            check
+                .check(&releases)
+                .iter()
+                .map(|s| s.status)
+                .collect::<Vec<_>>(),
+            vec![CheckStatus::UpToDate, CheckStatus::AheadOfLatest]
+        );
+    }
+
     #[test]
     fn test_deserialize_upgrade_pending_check_all_filled() {
         let expected = UpgradePendingCheck {
             name: "name-value".into(),
             current: "current-value".into(),
             latest: "latest-value".into(),
+            version_scheme: Some(VersionScheme::Loose),
+            constraint: VersionConstraint::parse("~1.4"),
+            normalize: serde_yaml::from_str("[pad_release]").unwrap(),
+            aliases: HashMap::from([("2024.1".into(), "24.1.0".into())]),
+            aliases_file: Some("aliases.yml".into()),
         };
         assert_de_tokens(
             &expected,
             &[
-                Token::Map { len: Some(3) },
+                Token::Map { len: Some(8) },
                 Token::Str("name"),
                 Token::Str("name-value"),
                 Token::Str("current"),
@@ -263,6 +807,30 @@ mod tests {
                 Token::Str("latest"),
                 Token::Some,
                 Token::Str("latest-value"),
+                Token::Str("version_scheme"),
+                Token::Some,
+                Token::UnitVariant {
+                    name: "VersionScheme",
+                    variant: "loose",
+                },
+                Token::Str("constraint"),
+                Token::Some,
+                Token::Str("~1.4"),
+                Token::Str("normalize"),
+                Token::Seq { len: Some(1) },
+                Token::UnitVariant {
+                    name: "NormalizationStep",
+                    variant: "pad_release",
+                },
+                Token::SeqEnd,
+                Token::Str("aliases"),
+                Token::Map { len: Some(1) },
+                Token::Str("2024.1"),
+                Token::Str("24.1.0"),
+                Token::MapEnd,
+                Token::Str("aliases_file"),
+                Token::Some,
+                Token::Str("aliases.yml"),
                 Token::MapEnd,
             ],
         );
@@ -274,6 +842,11 @@ mod tests {
             name: "name-value".into(),
             current: "current_name-value_release".into(),
             latest: "latest_name-value_release".into(),
+            version_scheme: None,
+            constraint: None,
+            normalize: Normalization::default(),
+            aliases: HashMap::new(),
+            aliases_file: None,
         };
         assert_de_tokens(
             &expected,
@@ -0,0 +1,281 @@
+use serde::{de::Unexpected, de::Visitor, Deserialize, Deserializer};
+
+use std::cmp::Ordering;
+
+use crate::version_scheme::{compare_release, release_components, VersionScheme};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Op {
+    Less,
+    LessEq,
+    Greater,
+    GreaterEq,
+    Tilde,
+    Caret,
+    /// Matches all versions starting with the given components, e.g. `1.4.*`.
+    Prefix,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Comparator {
+    op: Op,
+    release: Vec<u64>,
+}
+
+/// Increments the component at `index` and drops all following components,
+/// e.g. `bump([1, 4, 2], 1) == [1, 5]`.
+fn bump(release: &[u64], index: usize) -> Vec<u64> {
+    let mut bumped = release[..=index].to_vec();
+    bumped[index] += 1;
+    bumped
+}
+
+impl Comparator {
+    fn parse(comparator: &str) -> Option<Self> {
+        let comparator = comparator.trim();
+        let (op, rest) = [
+            (">=", Op::GreaterEq),
+            ("<=", Op::LessEq),
+            (">", Op::Greater),
+            ("<", Op::Less),
+            ("=", Op::Prefix),
+            ("~", Op::Tilde),
+            ("^", Op::Caret),
+        ]
+        .into_iter()
+        .find_map(|(prefix, op)| comparator.strip_prefix(prefix).map(|rest| (op, rest)))
+        .unwrap_or((Op::Caret, comparator));
+        let rest = rest.trim_start();
+        let rest = rest.strip_prefix(['v', 'V']).unwrap_or(rest);
+
+        let mut release = vec![];
+        let mut wildcard = false;
+        for component in rest.split('.') {
+            match component {
+                "*" | "x" | "X" => wildcard = true,
+                component if !wildcard => release.push(component.parse().ok()?),
+                _ => return None,
+            }
+        }
+        let op = match (op, wildcard) {
+            (_, false) if release.is_empty() => return None,
+            (Op::Caret | Op::Prefix, true) => Op::Prefix,
+            (_, true) => return None,
+            (op, false) => op,
+        };
+        Some(Self { op, release })
+    }
+
+    fn matches(&self, version: &str, scheme: VersionScheme) -> bool {
+        if self.op == Op::Prefix && self.release.is_empty() {
+            return true;
+        }
+        let Some(ordering) = scheme.compare(version, &format_bound(&self.release, scheme)) else {
+            return false;
+        };
+        let below = |bound: &[u64]| is_below(version, bound, scheme);
+        // Like in Cargo, a partial version like `1.4` stands for all versions
+        // starting with it, i.e. `<=1.4` means `<1.5` and `>1.4` means `>=1.5`.
+        let partial = self.release.len() < 3;
+        let next = || bump(&self.release, self.release.len() - 1);
+        match self.op {
+            Op::Less => below(&self.release),
+            Op::LessEq if partial => below(&next()),
+            Op::LessEq => ordering.is_eq() || below(&self.release),
+            Op::Greater if partial => scheme
+                .compare(version, &format_bound(&next(), scheme))
+                .is_some_and(Ordering::is_ge),
+            Op::Greater => ordering.is_gt(),
+            Op::GreaterEq => ordering.is_ge(),
+            Op::Tilde => {
+                ordering.is_ge() && below(&bump(&self.release, 1.min(self.release.len() - 1)))
+            }
+            Op::Caret => {
+                let index = self
+                    .release
+                    .iter()
+                    .position(|&component| component != 0)
+                    .unwrap_or(self.release.len() - 1);
+                ordering.is_ge() && below(&bump(&self.release, index))
+            }
+            Op::Prefix => ordering.is_ge() && below(&next()),
+        }
+    }
+}
+
+/// Formats release components as version of the scheme, e.g. `1.4.0` for
+/// `[1, 4]` in Semantic Versioning.
+fn format_bound(release: &[u64], scheme: VersionScheme) -> String {
+    let mut components: Vec<String> = release.iter().map(u64::to_string).collect();
+    if scheme == VersionScheme::Semver {
+        components.resize(3.max(components.len()), "0".into());
+    }
+    components.join(".")
+}
+
+/// Whether the version is ordered before the bound according to the scheme.
+/// Like in Cargo, pre-releases of the bound (e.g. `3.0.0-rc1` for `3.0`) are
+/// not considered below it.
+fn is_below(version: &str, bound: &[u64], scheme: VersionScheme) -> bool {
+    scheme.compare(version, &format_bound(bound, scheme)) == Some(Ordering::Less)
+        && compare_release(&release_components(version), bound).is_lt()
+}
+
+/// A constraint on versions in the syntax of Cargo's version requirements,
+/// e.g. `~1.4`, `^2`, `15.*`, or `>=1.2, <1.5`. Versions are compared with
+/// the bounds according to a version scheme.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VersionConstraint {
+    comparators: Vec<Comparator>,
+}
+
+impl VersionConstraint {
+    pub fn parse(constraint: &str) -> Option<Self> {
+        let comparators = constraint
+            .split(',')
+            .map(Comparator::parse)
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self { comparators })
+    }
+
+    /// Whether the version satisfies all comparators. Versions that cannot be
+    /// parsed according to the scheme never match.
+    pub fn matches(&self, version: &str, scheme: VersionScheme) -> bool {
+        scheme.compare(version, version).is_some()
+            && self
+                .comparators
+                .iter()
+                .all(|comparator| comparator.matches(version, scheme))
+    }
+}
+
+impl<'de> Deserialize<'de> for VersionConstraint {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(VersionConstraintVisitor)
+    }
+}
+
+struct VersionConstraintVisitor;
+
+impl<'de> Visitor<'de> for VersionConstraintVisitor {
+    type Value = VersionConstraint;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a version constraint like ~1.4, ^2, or <3.0")
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        VersionConstraint::parse(s)
+            .ok_or_else(|| serde::de::Error::invalid_value(Unexpected::Str(s), &self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_test::{assert_de_tokens_error, Token};
+
+    use crate::version_scheme::VersionScheme;
+
+    use super::VersionConstraint;
+
+    #[test]
+    fn test_version_constraint_matches() {
+        for (constraint, matching, not_matching) in [
+            (
+                "~1.4",
+                &["1.4", "1.4.0", "1.4.9-rc1"][..],
+                &["1.3.9", "1.5.0"][..],
+            ),
+            ("~1.4.2", &["1.4.2", "1.4.10"], &["1.4.1", "1.5.0"]),
+            ("~1", &["1.0", "1.9.9"], &["0.9", "2.0"]),
+            ("^2", &["2.0.0", "2.99"], &["1.9", "3.0.0"]),
+            ("^0.4.1", &["0.4.1", "0.4.9"], &["0.4.0", "0.5.0"]),
+            ("^0.0.3", &["0.0.3"], &["0.0.4"]),
+            ("1.4", &["1.4.0", "1.9"], &["1.3", "2.0"]),
+            ("<3.0", &["2.9.9", "2.0"], &["3.0.0", "3.0.0-rc1"]),
+            (">=1.2, <1.5", &["1.2", "1.4.9"], &["1.1.9", "1.5"]),
+            ("<=1.4", &["1.4", "1.4.5"], &["1.5.0", "1.5.0-rc1"]),
+            ("<=1.4.2", &["1.4.2", "1.4.1"], &["1.4.3", "1.4.2.1"]),
+            (">1.4", &["1.5.0", "2.0"], &["1.4.5", "1.4", "1.5.0-rc1"]),
+            (">1", &["2.0"], &["1.5.0", "1.0"]),
+            (">1.4.2", &["1.4.3", "1.4.2.1"], &["1.4.2", "1.4.1"]),
+            ("15.*", &["15", "15.4"], &["14.9", "16.0"]),
+            ("=15", &["15.0", "15.4"], &["16.0"]),
+            ("*", &["0.1", "15.4"], &["latest"]),
+        ] {
+            let parsed = VersionConstraint::parse(constraint).unwrap();
+            for version in matching {
+                assert!(
+                    parsed.matches(version, VersionScheme::Loose),
+                    "{version} matches {constraint}"
+                );
+            }
+            for version in not_matching {
+                assert!(
+                    !parsed.matches(version, VersionScheme::Loose),
+                    "{version} mismatches {constraint}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_version_constraint_uses_version_scheme() {
+        for (scheme, constraint, matching, not_matching) in [
+            (
+                VersionScheme::Semver,
+                "~1.4",
+                &["1.4.0", "1.4.9"][..],
+                &["1.4.0-rc.1", "1.5.0-rc.1", "1.4"][..],
+            ),
+            (
+                VersionScheme::Debian,
+                "^15",
+                &["15.3-1", "15.0~rc1"],
+                &["1:15.3-1", "16.0-1"],
+            ),
+            (
+                VersionScheme::Pep440,
+                ">=1.2, <2",
+                &["1.2", "1.9.post1"],
+                &["1.2rc1", "2.0rc1", "1!1.5"],
+            ),
+            (
+                VersionScheme::Maven,
+                "~1.4",
+                &["1.4", "1.4.2"],
+                &["1.4-SNAPSHOT", "1.5-SNAPSHOT"],
+            ),
+        ] {
+            let parsed = VersionConstraint::parse(constraint).unwrap();
+            for version in matching {
+                assert!(
+                    parsed.matches(version, scheme),
+                    "{version} matches {constraint} ({scheme:?})"
+                );
+            }
+            for version in not_matching {
+                assert!(
+                    !parsed.matches(version, scheme),
+                    "{version} mismatches {constraint} ({scheme:?})"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_deserialize_invalid_version_constraint() {
+        for constraint in ["", "~", "<1.*", "1.*.2", "1.a", ">=1,"] {
+            assert_de_tokens_error::<VersionConstraint>(
+                &[Token::Str(constraint)],
+                &format!("invalid value: string \"{constraint}\", expected a version constraint like ~1.4, ^2, or <3.0"),
+            );
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use std::io::Write;
+
+use flate2::{write::GzEncoder, Compression};
+
+/// Format in which the metrics are exposed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Format {
+    OpenMetrics,
+    /// The classic Prometheus text format, version 0.0.4.
+    Text,
+}
+
+/// Splits a header value like `Accept` into its items with their quality.
+fn weighted_items(header: &str) -> impl Iterator<Item = (String, f32)> + '_ {
+    header.split(',').filter_map(|item| {
+        let mut parts = item.split(';');
+        let value = parts.next()?.trim().to_ascii_lowercase();
+        if value.is_empty() {
+            return None;
+        }
+        let quality = parts
+            .filter_map(|param| param.split_once('='))
+            .find(|(key, _)| key.trim().eq_ignore_ascii_case("q"))
+            .map_or(Some(1.0), |(_, q)| q.trim().parse().ok())?;
+        Some((value, quality))
+    })
+}
+
+impl Format {
+    /// Selects the format based on the `Accept` header. OpenMetrics is only
+    /// used if it is explicitly accepted and not less preferred than the
+    /// text format.
+    pub fn negotiate(accept: Option<&str>) -> Self {
+        let mut open_metrics = 0.0f32;
+        let mut text = 0.0f32;
+        for (media_type, quality) in weighted_items(accept.unwrap_or("*/*")) {
+            match media_type.as_str() {
+                "application/openmetrics-text" => open_metrics = open_metrics.max(quality),
+                "text/plain" | "text/*" | "*/*" => text = text.max(quality),
+                _ => {}
+            }
+        }
+        if open_metrics > 0.0 && open_metrics >= text {
+            Format::OpenMetrics
+        } else {
+            Format::Text
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Format::OpenMetrics => "application/openmetrics-text; version=1.0.0; charset=utf-8",
+            Format::Text => "text/plain; version=0.0.4; charset=utf-8",
+        }
+    }
+
+    /// Converts metrics encoded in the OpenMetrics format into this format.
+    pub fn convert(&self, open_metrics: String) -> String {
+        match self {
+            Format::OpenMetrics => open_metrics,
+            Format::Text => to_text_format(&open_metrics),
+        }
+    }
+}
+
+/// Converts the OpenMetrics format into the Prometheus text format by
+/// dropping `UNIT` and `EOF` lines and using the sample names of counters and
+/// info metrics as metric names.
+fn to_text_format(open_metrics: &str) -> String {
+    let mut text = String::with_capacity(open_metrics.len());
+    let mut help = None;
+    for line in open_metrics.lines() {
+        if let Some(rest) = line.strip_prefix("# HELP ") {
+            help = Some(rest);
+        } else if let Some(rest) = line.strip_prefix("# TYPE ") {
+            let (name, metric_type) = rest.split_once(' ').unwrap_or((rest, "untyped"));
+            let (name, metric_type) = match metric_type {
+                "counter" => (format!("{name}_total"), "counter"),
+                "info" => (format!("{name}_info"), "gauge"),
+                "gaugehistogram" | "stateset" | "unknown" => (name.to_owned(), "untyped"),
+                metric_type => (name.to_owned(), metric_type),
+            };
+            if let Some(help) = help.take() {
+                let help_text = help.split_once(' ').map_or("", |(_, help_text)| help_text);
+                text.push_str(&format!("# HELP {name} {help_text}\n"));
+            }
+            text.push_str(&format!("# TYPE {name} {metric_type}\n"));
+        } else if line.starts_with("# UNIT ") || line == "# EOF" {
+            continue;
+        } else {
+            text.push_str(line);
+            text.push('\n');
+        }
+    }
+    text
+}
+
+/// Whether the `Accept-Encoding` header allows a gzip compressed response.
+pub fn accepts_gzip(accept_encoding: Option<&str>) -> bool {
+    accept_encoding.is_some_and(|accept_encoding| {
+        weighted_items(accept_encoding)
+            .any(|(coding, quality)| (coding == "gzip" || coding == "x-gzip") && quality > 0.0)
+    })
+}
+
+pub fn gzip(data: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data)?;
+    encoder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::GzDecoder;
+
+    use super::{accepts_gzip, gzip, Format};
+
+    #[test]
+    fn test_negotiate_format() {
+        for (accept, expected) in [
+            (None, Format::Text),
+            (Some("*/*"), Format::Text),
+            (Some("text/plain;version=0.0.4;q=1,*/*;q=0.1"), Format::Text),
+            (
+                Some("application/openmetrics-text;version=1.0.0,application/openmetrics-text;version=0.0.1;q=0.75,text/plain;version=0.0.4;q=0.5,*/*;q=0.1"),
+                Format::OpenMetrics,
+            ),
+            (
+                Some("application/openmetrics-text;q=0.5,text/plain"),
+                Format::Text,
+            ),
+            (Some("application/openmetrics-text;q=0"), Format::Text),
+        ] {
+            assert_eq!(Format::negotiate(accept), expected, "{accept:?}");
+        }
+    }
+
+    #[test]
+    fn test_convert_to_text_format() {
+        let open_metrics = "\
+# HELP release_exporter_build A metric with a constant '1' value.
+# TYPE release_exporter_build info
+release_exporter_build_info{version=\"0.5.1\"} 1
+# HELP release_provider_errors Number of failed fetches.
+# TYPE release_provider_errors counter
+release_provider_errors_total{provider=\"p\",error=\"timeout\"} 2
+# HELP release_cache_expiry_timestamp_seconds Expiry time.
+# TYPE release_cache_expiry_timestamp_seconds gauge
+# UNIT release_cache_expiry_timestamp_seconds seconds
+release_cache_expiry_timestamp_seconds{provider=\"p\"} 1700000000
+# EOF
+";
+        assert_eq!(
+            Format::Text.convert(open_metrics.into()),
+            "\
+# HELP release_exporter_build_info A metric with a constant '1' value.
+# TYPE release_exporter_build_info gauge
+release_exporter_build_info{version=\"0.5.1\"} 1
+# HELP release_provider_errors_total Number of failed fetches.
+# TYPE release_provider_errors_total counter
+release_provider_errors_total{provider=\"p\",error=\"timeout\"} 2
+# HELP release_cache_expiry_timestamp_seconds Expiry time.
+# TYPE release_cache_expiry_timestamp_seconds gauge
+release_cache_expiry_timestamp_seconds{provider=\"p\"} 1700000000
+"
+        );
+        assert_eq!(
+            Format::OpenMetrics.convert(open_metrics.into()),
+            open_metrics
+        );
+    }
+
+    #[test]
+    fn test_gzip() {
+        assert!(accepts_gzip(Some("gzip, deflate, br")));
+        assert!(!accepts_gzip(Some("gzip;q=0, deflate")));
+        assert!(!accepts_gzip(Some("identity")));
+        assert!(!accepts_gzip(None));
+
+        let compressed = gzip(b"metrics").unwrap();
+        let mut decompressed = String::new();
+        GzDecoder::new(compressed.as_slice())
+            .read_to_string(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, "metrics");
+    }
+}
@@ -7,23 +7,28 @@ use std::{
 
 use cache::{ReleaseCache, SystemClock};
 use clap::Parser;
-use metrics::Metrics;
+use metrics::{Metrics, MetricsConfig};
 use prometheus_client::{encoding::text::encode, metrics::info::Info, registry::Registry};
 use release_collection::ReleaseCollection;
 use reqwest::Client;
 use serde::Deserialize;
 
+mod api;
 mod baseurl;
 mod cache;
 mod checks;
+mod exposition;
 mod metrics;
+mod normalization;
 mod providers;
 mod release_collection;
+mod status_page;
 #[cfg(test)]
 mod test_config;
+mod version_scheme;
 
 use checks::upgrade_pending::UpgradePendingCheck;
-use providers::Provider;
+use providers::{Provider, VersionInfo};
 use tide::Server;
 
 #[derive(Parser, Debug)]
@@ -46,6 +51,24 @@ struct Args {
 struct Config {
     providers: Vec<Provider>,
     upgrade_pending_checks: Vec<UpgradePendingCheck>,
+    #[serde(default)]
+    metrics: MetricsConfig,
+}
+
+impl Config {
+    /// Uses the version scheme of the `current` provider for checks without
+    /// an explicitly configured version scheme.
+    fn resolve_version_schemes(&mut self) {
+        for check in &mut self.upgrade_pending_checks {
+            if check.version_scheme.is_none() {
+                check.version_scheme = self
+                    .providers
+                    .iter()
+                    .find(|p| p.name() == check.current)
+                    .map(Provider::version_scheme);
+            }
+        }
+    }
 }
 
 static APP_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"),);
@@ -57,18 +80,138 @@ struct State {
     metrics: Metrics,
     registry: Arc<Registry>,
     cache: Arc<Mutex<ReleaseCache<SystemTime, SystemClock>>>,
+    /// The error of the last fetch of each provider, if it failed.
+    errors: Arc<Mutex<HashMap<String, String>>>,
+    /// The time of the last successful fetch of each provider.
+    last_success: Arc<Mutex<HashMap<String, SystemTime>>>,
+}
+
+impl State {
+    /// Counts the providers whose versions are served from the cache and the
+    /// providers that need to be fetched.
+    fn observe_cache_lookups(&self) {
+        let mut cache = self.cache.lock().unwrap();
+        cache.expire();
+        for provider in &self.config.providers {
+            self.metrics
+                .observe_cache_lookup(provider.name(), cache.contains_key(provider.name()));
+        }
+    }
+
+    /// Fetches the versions of all providers not cached. Returns the versions
+    /// of all providers available.
+    async fn fetch(&self) -> HashMap<String, Vec<VersionInfo>> {
+        let State {
+            config,
+            http_client,
+            metrics,
+            registry: _,
+            cache,
+            errors,
+            last_success,
+        } = self;
+
+        // The cached versions are copied while the lock is held because other
+        // requests may expire them while the stale providers are fetched.
+        let mut cached = HashMap::new();
+        let mut stale = vec![];
+        {
+            let mut cache = cache.lock().unwrap();
+            cache.expire();
+            for provider in &config.providers {
+                match cache.get(provider.name()) {
+                    Some(versions) => {
+                        cached.insert(provider.name().to_owned(), versions.clone());
+                    }
+                    None => stale.push(provider.clone()),
+                }
+            }
+        }
+
+        let releases = ReleaseCollection::collect_from(stale, http_client).await;
+
+        let mut cache = cache.lock().unwrap();
+
+        for (provider, duration) in &releases.fetch_durations {
+            metrics.observe_provider_fetch(
+                provider,
+                *duration,
+                releases
+                    .errors
+                    .get(provider)
+                    .map(|error| providers::error::kind(error.as_ref())),
+            );
+        }
+
+        let mut errors = errors.lock().unwrap();
+        let mut last_success = last_success.lock().unwrap();
+        let now = SystemTime::now();
+        for provider in releases.releases.keys() {
+            errors.remove(provider);
+            last_success.insert(provider.clone(), now);
+        }
+        for (provider, error) in releases.errors {
+            tide::log::error!("Provider {} reported error: {}", provider, error);
+            errors.insert(provider, error.to_string());
+        }
+
+        for (key, release) in &releases.releases {
+            cache.insert(
+                key.clone(),
+                release.versions.clone(),
+                release.cache_duration,
+            );
+        }
+        let mut releases = releases
+            .releases
+            .into_iter()
+            .map(|(k, r)| (k, r.versions))
+            .collect::<HashMap<_, _>>();
+        releases.extend(cached);
+        releases
+    }
+
+    /// Updates the metrics describing the cache, the versions, and the checks.
+    fn update_metrics(&self, releases: &HashMap<String, Vec<VersionInfo>>) {
+        self.metrics
+            .update_cache(self.cache.lock().unwrap().expiries());
+        self.metrics
+            .update_versions(self.config.providers.iter().filter_map(|provider| {
+                releases.get(provider.name()).map(|versions| {
+                    (
+                        provider.name(),
+                        provider.version_scheme(),
+                        versions.as_slice(),
+                    )
+                })
+            }));
+        self.metrics.update(
+            self.config
+                .upgrade_pending_checks
+                .iter()
+                .map(|c| (c.name.as_str(), c.check(releases))),
+        );
+    }
 }
 
-fn create_app(config: Config, http_client: Client) -> Server<State> {
-    let mut registry = <Registry>::default();
+fn create_app(mut config: Config, http_client: Client) -> Server<State> {
+    config.resolve_version_schemes();
+
+    let mut registry = config.metrics.registry();
     registry.register(
         "release_exporter_build",
         "A metric with a constant '1' value labeled by version of the release-exporter",
         Info::new(vec![("version", env!("CARGO_PKG_VERSION"))]),
     );
 
-    let metrics = Metrics::new();
+    let metrics = Metrics::new(config.metrics.clone());
     metrics.register(&mut registry);
+    for provider in &mut config.providers {
+        let name = provider.name().to_owned();
+        if let Some(version_extractor) = provider.version_extractor_mut() {
+            version_extractor.set_rejected_counter(metrics.version_extraction_rejected(&name));
+        }
+    }
 
     let state = State {
         config,
@@ -76,66 +219,43 @@ fn create_app(config: Config, http_client: Client) -> Server<State> {
         metrics,
         registry: Arc::new(registry),
         cache: Arc::new(Mutex::new(ReleaseCache::new(SystemClock))),
+        errors: Arc::new(Mutex::new(HashMap::new())),
+        last_success: Arc::new(Mutex::new(HashMap::new())),
     };
 
     let mut app = tide::with_state(state);
     app.at("/metrics")
         .get(|req: tide::Request<State>| async move {
-            let State {
-                config,
-                http_client,
-                metrics,
-                registry,
-                cache,
-            } = req.state();
-
-            cache.lock().unwrap().expire();
-
-            let (cached, stale) = config
-                .providers
-                .iter()
-                .cloned()
-                .partition(|p| cache.lock().unwrap().contains_key(p.name()));
-
-            let releases = ReleaseCollection::collect_from(stale, http_client).await;
-
-            let mut cache = cache.lock().unwrap();
-
-            for (provider, error) in releases.errors {
-                tide::log::error!("Provider {} reported error: {}", provider, error);
-            }
-
-            for (key, release) in &releases.releases {
-                cache.insert(
-                    key.clone(),
-                    release.versions.clone(),
-                    release.cache_duration,
-                );
-            }
-            let mut releases = releases
-                .releases
-                .into_iter()
-                .map(|(k, r)| (k, r.versions))
-                .collect::<HashMap<_, _>>();
-            releases.extend(
-                cached
-                    .iter()
-                    .map(|p| (p.name().to_owned(), cache.get(p.name()).unwrap().clone())),
-            );
-            metrics.update(
-                config
-                    .upgrade_pending_checks
-                    .iter()
-                    .map(|c| (c.name.as_str(), c.check(&releases))),
+            let format =
+                exposition::Format::negotiate(req.header("Accept").map(|accept| accept.as_str()));
+            let compress = exposition::accepts_gzip(
+                req.header("Accept-Encoding")
+                    .map(|accept_encoding| accept_encoding.as_str()),
             );
+
+            let state = req.state();
+            state.observe_cache_lookups();
+            let releases = state.fetch().await;
+            state.update_metrics(&releases);
             let mut buffer = String::new();
-            encode(&mut buffer, registry).unwrap();
+            encode(&mut buffer, &state.registry).unwrap();
+            let buffer = format.convert(buffer);
 
-            Ok(tide::Response::builder(200)
-                .content_type("application/openmetrics-text; version=1.0.0; charset=utf-8")
-                .body(buffer)
-                .build())
+            let response = tide::Response::builder(200)
+                .content_type(format.content_type())
+                .header("Vary", "Accept, Accept-Encoding");
+            Ok(if compress {
+                response
+                    .header("Content-Encoding", "gzip")
+                    .body(exposition::gzip(buffer.as_bytes())?)
+                    .build()
+            } else {
+                response.body(buffer).build()
+            })
         });
+    app.at("/").get(status_page::index);
+    app.at("/api/v1/releases").get(api::releases);
+    app.at("/api/v1/checks").get(api::checks);
     app
 }
 
@@ -143,12 +263,20 @@ fn create_app(config: Config, http_client: Client) -> Server<State> {
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
     let config_file = File::open(args.config)?;
-    let config: Config = serde_yaml::from_reader(config_file)?;
+    let mut config: Config = serde_yaml::from_reader(config_file)?;
 
-    let http_client = reqwest::Client::builder()
-        .user_agent(APP_USER_AGENT)
-        .timeout(Duration::from_secs(args.http_timeout_seconds))
-        .build()?;
+    let client_builder = || {
+        reqwest::Client::builder()
+            .user_agent(APP_USER_AGENT)
+            .timeout(Duration::from_secs(args.http_timeout_seconds))
+    };
+    let http_client = client_builder().build()?;
+    for provider in &mut config.providers {
+        provider.configure_http_client(client_builder)?;
+    }
+    for check in &mut config.upgrade_pending_checks {
+        check.load_aliases_file()?;
+    }
 
     tide::log::start();
     let app = create_app(config, http_client);
@@ -157,8 +285,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
 #[cfg(test)]
 mod tests {
-    use std::time::Duration;
+    use std::{collections::HashMap, io::Read, time::Duration};
 
+    use flate2::read::GzDecoder;
     use regex::Regex;
     use reqwest::{Client, Url};
     use tide::{
@@ -169,13 +298,17 @@ mod tests {
     use crate::{
         checks::upgrade_pending::UpgradePendingCheck,
         create_app,
+        metrics::MetricsConfig,
+        normalization::Normalization,
         providers::{
             github::{self},
+            http_config::HttpConfig,
             prometheus,
             version_extractor::VersionExtractor,
             Provider,
         },
         test_config::{github_api_url, prometheus_api_url},
+        version_scheme::VersionScheme,
         Config,
     };
 
@@ -191,18 +324,29 @@ mod tests {
                             name: "dmarc-metrics-exporter".into(),
                         },
                         version_extractor: VersionExtractor::default(),
+                        version_scheme: VersionScheme::default(),
                         api_url: github_api_url(),
                     },
                     name: "latest_release".into(),
                     cache_duration: Duration::default(),
                 },
                 Provider::Prometheus {
-                    config: prometheus::Provider {
+                    config: Box::new(prometheus::Provider {
                         query: "dmarc_metrics_exporter_build_info".into(),
                         label: "version".into(),
+                        version_source: prometheus::VersionSource::Label,
+                        version_template: None,
+                        keep_labels: None,
+                        drop_labels: vec![],
                         version_extractor: VersionExtractor::default(),
+                        version_scheme: VersionScheme::default(),
                         api_url: prometheus_api_url(),
-                    },
+                        time: None,
+                        offset: Duration::default(),
+                        fail_on_warnings: false,
+                        http_config: HttpConfig::default(),
+                        http_client: None,
+                    }),
                     name: "current_release".into(),
                     cache_duration: Duration::default(),
                 },
@@ -211,7 +355,13 @@ mod tests {
                 name: "check_name".into(),
                 current: "current_release".into(),
                 latest: "latest_release".into(),
+                version_scheme: None,
+                constraint: None,
+                normalize: Normalization::default(),
+                aliases: HashMap::new(),
+                aliases_file: None,
             }],
+            metrics: MetricsConfig::default(),
         };
 
         let app = create_app(config, http_client);
@@ -224,6 +374,7 @@ mod tests {
             .unwrap();
 
         assert_eq!(response.status(), 200);
+        assert_eq!(response.content_type().unwrap().essence(), "text/plain");
         let body = response.body_string().await.unwrap();
         println!("{}", body);
         let metric_lines: Vec<&str> = body
@@ -234,5 +385,137 @@ mod tests {
         assert!(metric_lines.iter().any(|line| expected.is_match(line)));
         let expected = Regex::new("^release_exporter_build_info\\{version=\".+\"\\} 1$").unwrap();
         assert!(metric_lines.iter().any(|line| expected.is_match(line)));
+        assert!(
+            metric_lines.contains(&"release_info{provider=\"latest_release\",version=\"0.8.0\"} 1")
+        );
+
+        let mut request =
+            Request::new(Method::Get, Url::parse("http://localhost/metrics").unwrap());
+        request.insert_header("Accept", "application/openmetrics-text;version=1.0.0");
+        request.insert_header("Accept-Encoding", "gzip");
+        let mut response: Response = app.respond(request).await.unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            response.content_type().unwrap().essence(),
+            "application/openmetrics-text"
+        );
+        assert_eq!(response.header("Content-Encoding").unwrap(), "gzip");
+        let mut body = String::new();
+        GzDecoder::new(response.body_bytes().await.unwrap().as_slice())
+            .read_to_string(&mut body)
+            .unwrap();
+        assert!(body.ends_with("# EOF\n"));
+    }
+
+    #[tokio::test]
+    async fn test_prefix_matching_exporter_name() {
+        let config: Config = serde_yaml::from_str(
+            r#"
+            providers:
+              - name: app_release
+                provider: static
+                versions:
+                  - version: 1.0.0
+            upgrade_pending_checks:
+              - name: app
+                current: app_release
+                latest: app_release
+            metrics:
+              prefix: release_exporter
+            "#,
+        )
+        .unwrap();
+        let app = create_app(config, Client::new());
+        let mut response: Response = app
+            .respond(Request::new(
+                Method::Get,
+                Url::parse("http://localhost/metrics").unwrap(),
+            ))
+            .await
+            .unwrap();
+        let body = response.body_string().await.unwrap();
+        let names: Vec<&str> = body
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(|line| line.split(['{', ' ']).next())
+            .collect();
+        for expected in [
+            "release_exporter_build_info",
+            "release_info",
+            "release_cache_entries",
+            "release_exporter_upgrades",
+            "release_exporter_upgrades_by_status",
+        ] {
+            assert!(names.contains(&expected), "{expected} in {names:?}");
+        }
+        assert!(
+            names
+                .iter()
+                .all(|name| !name.starts_with("release_exporter_release")),
+            "{names:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejected_versions_only_counted_for_extracting_providers() {
+        let config: Config = serde_yaml::from_str(
+            r#"
+            providers:
+              - name: static_release
+                provider: static
+                versions: []
+              - name: unreachable
+                provider: prometheus
+                api_url: http://127.0.0.1:1/
+                query: build_info
+                label: version
+            upgrade_pending_checks: []
+            "#,
+        )
+        .unwrap();
+        let app = create_app(config, Client::new());
+        let mut response: Response = app
+            .respond(Request::new(
+                Method::Get,
+                Url::parse("http://localhost/metrics").unwrap(),
+            ))
+            .await
+            .unwrap();
+        let body = response.body_string().await.unwrap();
+        assert!(body.contains("version_extraction_rejected_total{provider=\"unreachable\"} 0"));
+        assert!(!body.contains("version_extraction_rejected_total{provider=\"static_release\"}"));
+    }
+
+    #[test]
+    fn test_resolve_version_schemes() {
+        let mut config: Config = serde_yaml::from_str(
+            r#"
+            providers:
+              - name: current_app_release
+                provider: static
+                version_scheme: pep440
+                versions: []
+            upgrade_pending_checks:
+              - name: app
+              - name: explicit
+                current: current_app_release
+                version_scheme: loose
+              - name: unknown_provider
+            "#,
+        )
+        .unwrap();
+        config.resolve_version_schemes();
+        assert_eq!(
+            config
+                .upgrade_pending_checks
+                .iter()
+                .map(|c| c.version_scheme)
+                .collect::<Vec<_>>(),
+            vec![
+                Some(VersionScheme::Pep440),
+                Some(VersionScheme::Loose),
+                None
+            ]
+        );
     }
 }
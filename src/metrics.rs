@@ -1,18 +1,152 @@
-use std::fmt::Write;
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    fmt::Write,
+    hash::Hash,
+    sync::{
+        atomic::{AtomicI64, AtomicU64},
+        Arc, Mutex,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 use prometheus_client::{
     encoding::{EncodeLabel, EncodeLabelSet, EncodeLabelValue, LabelSetEncoder},
-    metrics::{family::Family, gauge::Gauge},
-    registry::Registry,
+    metrics::{
+        counter::Counter,
+        family::Family,
+        gauge::{Atomic, Gauge},
+        histogram::{exponential_buckets, Histogram},
+    },
+    registry::{Registry, Unit},
 };
+use serde::Deserialize;
 
-use crate::checks::upgrade_pending::{CheckStatus, LabeledStatus};
+use crate::{
+    checks::upgrade_pending::{CheckStatus, LabeledStatus},
+    providers::VersionInfo,
+    version_scheme::{Severity, VersionScheme},
+};
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 struct UpgradeLabels {
     status: CheckStatus,
     name: String,
     latest_version: Option<String>,
+    unconstrained_latest_version: Option<String>,
+    severity: Option<Severity>,
+    additional_labels: Vec<(String, String)>,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+struct LatestVersionLabels {
+    name: String,
+    latest_version: Option<String>,
+    unconstrained_latest_version: Option<String>,
+    severity: Option<Severity>,
+    additional_labels: Vec<(String, String)>,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+struct CheckLabels {
+    name: String,
+    additional_labels: Vec<(String, String)>,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct CheckStatusLabels {
+    name: String,
+    status: CheckStatus,
+}
+
+const CHECK_STATUSES: [CheckStatus; 5] = [
+    CheckStatus::Unknown,
+    CheckStatus::UpToDate,
+    CheckStatus::UpgradeAvailable,
+    CheckStatus::AheadOfLatest,
+    CheckStatus::Incomparable,
+];
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct ProviderLabels {
+    provider: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct ProviderErrorLabels {
+    provider: String,
+    error: &'static str,
+}
+
+/// The label sets of a [`GaugeFamily`] set in the previous and the ongoing
+/// update.
+#[derive(Debug)]
+struct Series<S> {
+    previous: HashSet<S>,
+    current: HashSet<S>,
+}
+
+/// A family of gauges replaced by each update. Series set again keep their
+/// identity, while series not set anymore are removed by
+/// [`GaugeFamily::remove_stale`].
+struct GaugeFamily<S, N = i64, A = AtomicI64> {
+    family: Family<S, Gauge<N, A>>,
+    series: Arc<Mutex<Series<S>>>,
+}
+
+impl<S, N, A> Clone for GaugeFamily<S, N, A> {
+    fn clone(&self) -> Self {
+        Self {
+            family: self.family.clone(),
+            series: self.series.clone(),
+        }
+    }
+}
+
+impl<S: Clone + Hash + Eq, N, A: Default> Default for GaugeFamily<S, N, A> {
+    fn default() -> Self {
+        Self {
+            family: Family::default(),
+            series: Arc::new(Mutex::new(Series {
+                previous: HashSet::new(),
+                current: HashSet::new(),
+            })),
+        }
+    }
+}
+
+impl<S: Clone + Hash + Eq, N, A: Atomic<N> + Default> GaugeFamily<S, N, A> {
+    fn get_or_create(&self, labels: &S) -> Gauge<N, A> {
+        self.series.lock().unwrap().current.insert(labels.clone());
+        self.family.get_or_create(labels).clone()
+    }
+
+    /// Removes the series not set since the previous call.
+    fn remove_stale(&self) {
+        let mut series = self.series.lock().unwrap();
+        let current = std::mem::take(&mut series.current);
+        for labels in series.previous.difference(&current) {
+            self.family.remove(labels);
+        }
+        series.previous = current;
+    }
+}
+
+fn fetch_duration_histogram() -> Histogram {
+    Histogram::new(exponential_buckets(0.05, 2.0, 10))
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+struct VersionComponentLabels {
+    provider: String,
+    component: &'static str,
+    additional_labels: Vec<(String, String)>,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+struct ReleaseInfoLabels {
+    provider: String,
+    version: String,
     additional_labels: Vec<(String, String)>,
 }
 
@@ -21,11 +155,16 @@ impl EncodeLabelValue for CheckStatus {
         &self,
         encoder: &mut prometheus_client::encoding::LabelValueEncoder,
     ) -> Result<(), std::fmt::Error> {
-        encoder.write_str(match self {
-            CheckStatus::Unknown => "unknown",
-            CheckStatus::UpToDate => "up-to-date",
-            CheckStatus::UpgradeAvailable => "upgrade-available",
-        })
+        encoder.write_str(self.as_str())
+    }
+}
+
+impl EncodeLabelValue for Severity {
+    fn encode(
+        &self,
+        encoder: &mut prometheus_client::encoding::LabelValueEncoder,
+    ) -> Result<(), std::fmt::Error> {
+        encoder.write_str(self.as_str())
     }
 }
 
@@ -36,6 +175,49 @@ impl EncodeLabelSet for UpgradeLabels {
         if let Some(latest_version) = &self.latest_version {
             ("latest_version", latest_version.as_str()).encode(encoder.encode_label())?;
         }
+        if let Some(unconstrained_latest_version) = &self.unconstrained_latest_version {
+            (
+                "unconstrained_latest_version",
+                unconstrained_latest_version.as_str(),
+            )
+                .encode(encoder.encode_label())?;
+        }
+        if let Some(severity) = self.severity {
+            ("severity", severity).encode(encoder.encode_label())?;
+        }
+        for label in &self.additional_labels {
+            (label.0.as_str(), label.1.as_str()).encode(encoder.encode_label())?;
+        }
+        Ok(())
+    }
+}
+
+impl EncodeLabelSet for LatestVersionLabels {
+    fn encode(&self, mut encoder: LabelSetEncoder) -> Result<(), std::fmt::Error> {
+        ("name", self.name.as_str()).encode(encoder.encode_label())?;
+        if let Some(latest_version) = &self.latest_version {
+            ("latest_version", latest_version.as_str()).encode(encoder.encode_label())?;
+        }
+        if let Some(unconstrained_latest_version) = &self.unconstrained_latest_version {
+            (
+                "unconstrained_latest_version",
+                unconstrained_latest_version.as_str(),
+            )
+                .encode(encoder.encode_label())?;
+        }
+        if let Some(severity) = self.severity {
+            ("severity", severity).encode(encoder.encode_label())?;
+        }
+        for label in &self.additional_labels {
+            (label.0.as_str(), label.1.as_str()).encode(encoder.encode_label())?;
+        }
+        Ok(())
+    }
+}
+
+impl EncodeLabelSet for CheckLabels {
+    fn encode(&self, mut encoder: LabelSetEncoder) -> Result<(), std::fmt::Error> {
+        ("name", self.name.as_str()).encode(encoder.encode_label())?;
         for label in &self.additional_labels {
             (label.0.as_str(), label.1.as_str()).encode(encoder.encode_label())?;
         }
@@ -43,71 +225,552 @@ impl EncodeLabelSet for UpgradeLabels {
     }
 }
 
+impl EncodeLabelSet for VersionComponentLabels {
+    fn encode(&self, mut encoder: LabelSetEncoder) -> Result<(), std::fmt::Error> {
+        ("provider", self.provider.as_str()).encode(encoder.encode_label())?;
+        ("component", self.component).encode(encoder.encode_label())?;
+        for label in &self.additional_labels {
+            (label.0.as_str(), label.1.as_str()).encode(encoder.encode_label())?;
+        }
+        Ok(())
+    }
+}
+
+impl EncodeLabelSet for ReleaseInfoLabels {
+    fn encode(&self, mut encoder: LabelSetEncoder) -> Result<(), std::fmt::Error> {
+        ("provider", self.provider.as_str()).encode(encoder.encode_label())?;
+        ("version", self.version.as_str()).encode(encoder.encode_label())?;
+        for label in &self.additional_labels {
+            (label.0.as_str(), label.1.as_str()).encode(encoder.encode_label())?;
+        }
+        Ok(())
+    }
+}
+
+/// Replaces characters not allowed in Prometheus label names with `_`.
+fn sanitize_label_name(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !sanitized.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+/// Names of the labels set by the exporter itself. Provider labels with these
+/// names are renamed to avoid duplicate labels. They are renamed on all
+/// metrics, even those without the built-in label, so that a provider label
+/// has the same name on every metric, e.g. for joins.
+const BUILT_IN_LABELS: [&str; 8] = [
+    "name",
+    "status",
+    "latest_version",
+    "unconstrained_latest_version",
+    "severity",
+    "provider",
+    "component",
+    "version",
+];
+
+fn is_valid_label_name(name: &str) -> bool {
+    sanitize_label_name(name) == name
+}
+
+fn is_valid_metric_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct MetricsConfigFields {
+    #[serde(default)]
+    latest_version_info: bool,
+    prefix: Option<String>,
+    #[serde(default)]
+    const_labels: HashMap<String, String>,
+}
+
+/// Options for the exported metrics.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(try_from = "MetricsConfigFields")]
+pub struct MetricsConfig {
+    /// Export the latest versions of checks as separate
+    /// `upgrades_latest_version_info` metric instead of labels of `upgrades`.
+    pub latest_version_info: bool,
+    /// Prefix of all metric names, joined with an underscore.
+    pub prefix: Option<String>,
+    /// Labels added to all metrics.
+    pub const_labels: HashMap<String, String>,
+}
+
+impl TryFrom<MetricsConfigFields> for MetricsConfig {
+    type Error = String;
+
+    fn try_from(config: MetricsConfigFields) -> Result<Self, Self::Error> {
+        if let Some(prefix) = &config.prefix {
+            if !is_valid_metric_name(prefix) {
+                return Err(format!("invalid metric name prefix: {prefix}"));
+            }
+        }
+        for name in config.const_labels.keys() {
+            if !is_valid_label_name(name) || name.starts_with("__") {
+                return Err(format!("invalid constant label name: {name}"));
+            }
+            if BUILT_IN_LABELS.contains(&name.as_str()) {
+                return Err(format!(
+                    "constant label conflicts with built-in label: {name}"
+                ));
+            }
+        }
+        Ok(Self {
+            latest_version_info: config.latest_version_info,
+            prefix: config.prefix,
+            const_labels: config.const_labels,
+        })
+    }
+}
+
+impl MetricsConfig {
+    /// Creates a registry applying the constant labels. The prefix is applied
+    /// by [`Metrics::register`].
+    pub fn registry(&self) -> Registry {
+        let mut const_labels: Vec<_> = self
+            .const_labels
+            .iter()
+            .map(|(name, value)| (Cow::Owned(name.clone()), Cow::Owned(value.clone())))
+            .collect();
+        const_labels.sort();
+        Registry::with_labels(const_labels.into_iter())
+    }
+}
+
 #[derive(Clone)]
 pub struct Metrics {
-    upgrades: Family<UpgradeLabels, Gauge>,
+    config: MetricsConfig,
+    upgrades: GaugeFamily<UpgradeLabels>,
+    latest_version_info: GaugeFamily<LatestVersionLabels>,
+    upgrade_available: GaugeFamily<CheckLabels, f64, AtomicU64>,
+    upgrades_by_status: GaugeFamily<CheckStatusLabels>,
+    releases_behind: GaugeFamily<CheckLabels>,
+    latest_release_timestamp: GaugeFamily<CheckLabels>,
+    upgrade_pending_since_timestamp: GaugeFamily<CheckLabels>,
+    version_extraction_rejected: Family<ProviderLabels, Counter>,
+    version_components: GaugeFamily<VersionComponentLabels>,
+    release_info: GaugeFamily<ReleaseInfoLabels>,
+    provider_up: Family<ProviderLabels, Gauge>,
+    provider_fetch_duration: Family<ProviderLabels, Histogram, fn() -> Histogram>,
+    provider_last_success_timestamp: Family<ProviderLabels, Gauge>,
+    provider_errors: Family<ProviderErrorLabels, Counter>,
+    cache_hits: Family<ProviderLabels, Counter>,
+    cache_misses: Family<ProviderLabels, Counter>,
+    cache_entries: Gauge,
+    cache_expiry_timestamp: GaugeFamily<ProviderLabels>,
 }
 
 impl Metrics {
-    pub fn new() -> Self {
+    /// The labels of a provider with sanitized names. Labels conflicting with
+    /// built-in labels, constant labels, or other provider labels after
+    /// sanitization are prefixed with `exported_`, like Prometheus does with
+    /// `honor_labels: false`.
+    fn additional_labels(&self, labels: &HashMap<String, String>) -> Vec<(String, String)> {
+        let mut taken: HashSet<String> = BUILT_IN_LABELS
+            .iter()
+            .map(|name| name.to_string())
+            .chain(self.config.const_labels.keys().cloned())
+            .collect();
+        // Valid label names take precedence over sanitized ones.
+        let mut labels: Vec<_> = labels.iter().collect();
+        labels.sort_by_key(|(name, _)| (!is_valid_label_name(name), *name));
+        labels
+            .into_iter()
+            .map(|(name, value)| {
+                let mut name = sanitize_label_name(name);
+                while !taken.insert(name.clone()) {
+                    name = format!("exported_{name}");
+                }
+                (name, value.clone())
+            })
+            .collect()
+    }
+
+    pub fn new(config: MetricsConfig) -> Self {
         Self {
-            upgrades: Family::default(),
+            config,
+            upgrades: GaugeFamily::default(),
+            latest_version_info: GaugeFamily::default(),
+            upgrade_available: GaugeFamily::default(),
+            upgrades_by_status: GaugeFamily::default(),
+            releases_behind: GaugeFamily::default(),
+            latest_release_timestamp: GaugeFamily::default(),
+            upgrade_pending_since_timestamp: GaugeFamily::default(),
+            version_extraction_rejected: Family::default(),
+            version_components: GaugeFamily::default(),
+            release_info: GaugeFamily::default(),
+            provider_up: Family::default(),
+            provider_fetch_duration: Family::new_with_constructor(fetch_duration_histogram),
+            provider_last_success_timestamp: Family::default(),
+            provider_errors: Family::default(),
+            cache_hits: Family::default(),
+            cache_misses: Family::default(),
+            cache_entries: Gauge::default(),
+            cache_expiry_timestamp: GaugeFamily::default(),
+        }
+    }
+
+    /// Counts a cache lookup of the versions of a provider.
+    pub fn observe_cache_lookup(&self, provider: &str, hit: bool) {
+        let labels = ProviderLabels {
+            provider: provider.into(),
+        };
+        if hit {
+            self.cache_hits.get_or_create(&labels).inc();
+        } else {
+            self.cache_misses.get_or_create(&labels).inc();
+        }
+    }
+
+    /// Exports the cached providers with the expiry times of their entries.
+    pub fn update_cache<'a, I>(&self, expiries: I)
+    where
+        I: Iterator<Item = (&'a str, &'a SystemTime)>,
+    {
+        let mut entries = 0;
+        for (provider, expiry) in expiries {
+            let expiry = expiry.duration_since(UNIX_EPOCH).unwrap_or_default();
+            self.cache_expiry_timestamp
+                .get_or_create(&ProviderLabels {
+                    provider: provider.into(),
+                })
+                .set(expiry.as_secs().try_into().unwrap_or(i64::MAX));
+            entries += 1;
         }
+        self.cache_expiry_timestamp.remove_stale();
+        self.cache_entries.set(entries);
+    }
+
+    /// Records the outcome of fetching the versions of a provider. The
+    /// `error_kind` is `None` if the fetch succeeded.
+    pub fn observe_provider_fetch(
+        &self,
+        provider: &str,
+        duration: Duration,
+        error_kind: Option<&'static str>,
+    ) {
+        let labels = ProviderLabels {
+            provider: provider.into(),
+        };
+        self.provider_fetch_duration
+            .get_or_create(&labels)
+            .observe(duration.as_secs_f64());
+        match error_kind {
+            None => {
+                self.provider_up.get_or_create(&labels).set(1);
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default();
+                self.provider_last_success_timestamp
+                    .get_or_create(&labels)
+                    .set(now.as_secs().try_into().unwrap_or(i64::MAX));
+            }
+            Some(error) => {
+                self.provider_up.get_or_create(&labels).set(0);
+                self.provider_errors
+                    .get_or_create(&ProviderErrorLabels {
+                        provider: provider.into(),
+                        error,
+                    })
+                    .inc();
+            }
+        }
+    }
+
+    /// Counter of values rejected by the version extractor of a provider.
+    pub fn version_extraction_rejected(&self, provider: &str) -> Counter {
+        self.version_extraction_rejected
+            .get_or_create(&ProviderLabels {
+                provider: provider.into(),
+            })
+            .clone()
     }
 
     pub fn update<'a, I>(&self, check_results: I)
     where
         I: Iterator<Item = (&'a str, Vec<LabeledStatus<'a>>)>,
     {
-        self.upgrades.clear();
         for (name, releases) in check_results {
+            for status in CHECK_STATUSES {
+                self.upgrades_by_status
+                    .get_or_create(&CheckStatusLabels {
+                        name: name.into(),
+                        status,
+                    })
+                    .set(0);
+            }
             for release in releases {
+                let additional_labels = self.additional_labels(release.labels);
+                let check_labels = CheckLabels {
+                    name: name.into(),
+                    additional_labels: additional_labels.clone(),
+                };
+                self.upgrade_available
+                    .get_or_create(&check_labels)
+                    .set(match release.status {
+                        CheckStatus::UpgradeAvailable => 1.0,
+                        CheckStatus::UpToDate | CheckStatus::AheadOfLatest => 0.0,
+                        CheckStatus::Unknown | CheckStatus::Incomparable => f64::NAN,
+                    });
+                self.upgrades_by_status
+                    .get_or_create(&CheckStatusLabels {
+                        name: name.into(),
+                        status: release.status,
+                    })
+                    .inc();
+                if let Some(releases_behind) = release.releases_behind {
+                    self.releases_behind
+                        .get_or_create(&check_labels)
+                        .set(releases_behind.try_into().unwrap_or(i64::MAX));
+                }
+                if let Some(published_at) = release.latest_published_at {
+                    self.latest_release_timestamp
+                        .get_or_create(&check_labels)
+                        .set(published_at.timestamp());
+                }
+                if let Some(pending_since) = release.upgrade_pending_since {
+                    self.upgrade_pending_since_timestamp
+                        .get_or_create(&check_labels)
+                        .set(pending_since.timestamp());
+                }
+                let mut latest_version = release.latest_version.map(String::from);
+                let mut unconstrained_latest_version =
+                    release.unconstrained_latest_version.map(String::from);
+                let mut severity = release.severity;
+                if self.config.latest_version_info
+                    && (latest_version.is_some() || unconstrained_latest_version.is_some())
+                {
+                    self.latest_version_info
+                        .get_or_create(&LatestVersionLabels {
+                            name: name.into(),
+                            latest_version: latest_version.take(),
+                            unconstrained_latest_version: unconstrained_latest_version.take(),
+                            severity: severity.take(),
+                            additional_labels: additional_labels.clone(),
+                        })
+                        .set(1);
+                }
                 self.upgrades
                     .get_or_create(&UpgradeLabels {
                         name: name.into(),
                         status: release.status,
-                        latest_version: release.latest_version.map(String::from),
-                        additional_labels: release
-                            .labels
-                            .iter()
-                            .map(|item| (item.0.clone(), item.1.clone()))
-                            .collect(),
+                        latest_version,
+                        unconstrained_latest_version,
+                        severity,
+                        additional_labels,
                     })
                     .set(1);
             }
         }
+        self.upgrades.remove_stale();
+        self.latest_version_info.remove_stale();
+        self.upgrade_available.remove_stale();
+        self.upgrades_by_status.remove_stale();
+        self.releases_behind.remove_stale();
+        self.latest_release_timestamp.remove_stale();
+        self.upgrade_pending_since_timestamp.remove_stale();
     }
 
+    /// Exports the versions of each provider and their major, minor, and patch
+    /// components according to the version scheme of the provider. Of multiple
+    /// versions with the same labels only the components of the first one are
+    /// exported.
+    pub fn update_versions<'a, I>(&self, releases: I)
+    where
+        I: Iterator<Item = (&'a str, VersionScheme, &'a [VersionInfo])>,
+    {
+        for (provider, scheme, versions) in releases {
+            let mut seen = HashSet::new();
+            for version_info in versions {
+                let mut additional_labels = self.additional_labels(&version_info.labels);
+                additional_labels.sort();
+                let Some(version) = &version_info.version else {
+                    continue;
+                };
+                self.release_info
+                    .get_or_create(&ReleaseInfoLabels {
+                        provider: provider.into(),
+                        version: version.clone(),
+                        additional_labels: additional_labels.clone(),
+                    })
+                    .set(1);
+                if !seen.insert(additional_labels.clone()) {
+                    continue;
+                }
+                for (component, value) in ["major", "minor", "patch"]
+                    .into_iter()
+                    .zip(scheme.release_components(version))
+                {
+                    self.version_components
+                        .get_or_create(&VersionComponentLabels {
+                            provider: provider.into(),
+                            component,
+                            additional_labels: additional_labels.clone(),
+                        })
+                        .set(value.try_into().unwrap_or(i64::MAX));
+                }
+            }
+        }
+        self.version_components.remove_stale();
+        self.release_info.remove_stale();
+    }
+
+    /// Registers all metrics. The prefix is only applied to the metrics of
+    /// checks because the `release_` metrics are namespaced already.
     pub fn register(&self, registry: &mut Registry) {
+        registry.register(
+            "release_version_component",
+            "Numeric major, minor, and patch components of the versions returned by providers",
+            self.version_components.family.clone(),
+        );
+        registry.register(
+            "release_info",
+            "A metric with a constant '1' value labeled by the versions returned by providers",
+            self.release_info.family.clone(),
+        );
+        registry.register(
+            "release_provider_up",
+            "Whether the last fetch of the versions of a provider succeeded",
+            self.provider_up.clone(),
+        );
+        registry.register_with_unit(
+            "release_provider_fetch_duration",
+            "Duration of fetching the versions of a provider",
+            Unit::Seconds,
+            self.provider_fetch_duration.clone(),
+        );
+        registry.register_with_unit(
+            "release_provider_last_success_timestamp",
+            "Time of the last successful fetch of the versions of a provider as Unix timestamp",
+            Unit::Seconds,
+            self.provider_last_success_timestamp.clone(),
+        );
+        registry.register(
+            "release_provider_errors",
+            "Number of failed fetches of the versions of a provider by type of error",
+            self.provider_errors.clone(),
+        );
+        registry.register(
+            "release_cache_hits",
+            "Number of times the versions of a provider were served from the cache",
+            self.cache_hits.clone(),
+        );
+        registry.register(
+            "release_cache_misses",
+            "Number of times the versions of a provider were not cached and had to be fetched",
+            self.cache_misses.clone(),
+        );
+        registry.register(
+            "release_cache_entries",
+            "Number of providers with cached versions",
+            self.cache_entries.clone(),
+        );
+        registry.register_with_unit(
+            "release_cache_expiry_timestamp",
+            "Expiry time of the cached versions of a provider as Unix timestamp",
+            Unit::Seconds,
+            self.cache_expiry_timestamp.family.clone(),
+        );
+
+        let registry = match &self.config.prefix {
+            Some(prefix) => registry.sub_registry_with_prefix(prefix),
+            None => registry,
+        };
         registry.register(
             "upgrades",
-            "Count of different upgrade states (unknown, up-to-date, upgrade-available)",
-            self.upgrades.clone(),
+            "Count of different upgrade states (unknown, up-to-date, upgrade-available, ahead-of-latest, incomparable)",
+            self.upgrades.family.clone(),
+        );
+        if self.config.latest_version_info {
+            registry.register(
+                "upgrades_latest_version_info",
+                "A metric with a constant '1' value labeled by the latest version of a check",
+                self.latest_version_info.family.clone(),
+            );
+        }
+        registry.register(
+            "upgrade_available",
+            "Whether an upgrade is available (1), not available (0), or unknown (NaN)",
+            self.upgrade_available.family.clone(),
+        );
+        registry.register(
+            "upgrades_by_status",
+            "Number of current versions of a check by upgrade status",
+            self.upgrades_by_status.family.clone(),
+        );
+        registry.register(
+            "upgrades_releases_behind",
+            "Number of releases newer than the current version up to the latest version",
+            self.releases_behind.family.clone(),
+        );
+        registry.register(
+            "latest_release_timestamp_seconds",
+            "Publication time of the latest release as Unix timestamp",
+            self.latest_release_timestamp.family.clone(),
+        );
+        registry.register(
+            "upgrade_pending_since_timestamp_seconds",
+            "Publication time of the oldest release newer than the current version as Unix timestamp",
+            self.upgrade_pending_since_timestamp.family.clone(),
+        );
+        registry.register(
+            "version_extraction_rejected",
+            "Number of values rejected by a provider for not matching any version extraction rule",
+            self.version_extraction_rejected.clone(),
         );
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::{
+        collections::HashMap,
+        time::{Duration, UNIX_EPOCH},
+    };
 
     use prometheus_client::{encoding::text::encode, registry::Registry};
 
-    use crate::checks::upgrade_pending::{CheckStatus, LabeledStatus};
+    use crate::{
+        checks::upgrade_pending::{CheckStatus, LabeledStatus},
+        providers::{ReleaseMetadata, VersionInfo},
+        test_config::labeled_status,
+        version_scheme::{Severity, VersionScheme},
+    };
 
-    use super::Metrics;
+    use super::{sanitize_label_name, Metrics, MetricsConfig};
+
+    /// Registers the metrics with the registry and returns the lines of the
+    /// encoded registry.
+    fn encode_lines(metrics: &Metrics, mut registry: Registry) -> Vec<String> {
+        metrics.register(&mut registry);
+        let mut buffer = String::new();
+        encode(&mut buffer, &registry).unwrap();
+        buffer.lines().map(String::from).collect()
+    }
 
     #[test]
     fn test_update_metrics() {
-        let metrics = Metrics::new();
+        let metrics = Metrics::new(MetricsConfig::default());
         let labels = HashMap::from([("label".into(), "label-value".into())]);
 
         let check_results = [(
             "check_name",
             vec![LabeledStatus {
-                labels: &labels,
-                status: CheckStatus::UpToDate,
-                latest_version: "current-version".into(),
+                latest_version: Some("current-version"),
+                releases_behind: Some(0),
+                ..labeled_status(&labels, CheckStatus::UpToDate)
             }],
         )];
         metrics.update(check_results.into_iter());
@@ -115,25 +778,348 @@ mod tests {
         let check_results = [(
             "check_name",
             vec![LabeledStatus {
-                labels: &labels,
-                status: CheckStatus::UpgradeAvailable,
-                latest_version: "latest-version".into(),
+                severity: Some(Severity::Major),
+                latest_version: Some("latest-version"),
+                releases_behind: Some(2),
+                latest_published_at: Some("2024-01-20T00:00:00Z".parse().unwrap()),
+                upgrade_pending_since: Some("2024-01-05T00:00:00Z".parse().unwrap()),
+                unconstrained_latest_version: Some("unconstrained-version"),
+                ..labeled_status(&labels, CheckStatus::UpgradeAvailable)
             }],
         )];
         metrics.update(check_results.into_iter());
 
-        let mut registry = <Registry>::default();
-        metrics.register(&mut registry);
-        let mut buffer = String::new();
-        encode(&mut buffer, &registry).unwrap();
-
-        let buffer: String = buffer
-            .split('\n')
+        let lines: Vec<String> = encode_lines(&metrics, Registry::default())
+            .into_iter()
             .filter(|line| !line.starts_with('#'))
+            .filter(|line| !line.starts_with("upgrade_available"))
+            .filter(|line| !line.starts_with("upgrades_by_status"))
             .collect();
         assert_eq!(
-            buffer,
-            "upgrades{status=\"upgrade-available\",name=\"check_name\",latest_version=\"latest-version\",label=\"label-value\"} 1"
+            lines,
+            vec![
+                "release_cache_entries 0",
+                "upgrades{status=\"upgrade-available\",name=\"check_name\",latest_version=\"latest-version\",unconstrained_latest_version=\"unconstrained-version\",severity=\"major\",label=\"label-value\"} 1",
+                "upgrades_releases_behind{name=\"check_name\",label=\"label-value\"} 2",
+                "latest_release_timestamp_seconds{name=\"check_name\",label=\"label-value\"} 1705708800",
+                "upgrade_pending_since_timestamp_seconds{name=\"check_name\",label=\"label-value\"} 1704412800",
+            ]
         );
     }
+
+    #[test]
+    fn test_latest_version_info() {
+        let metrics = Metrics::new(MetricsConfig {
+            latest_version_info: true,
+            ..MetricsConfig::default()
+        });
+        let labels = HashMap::from([("label".into(), "label-value".into())]);
+        let check_results = [(
+            "check_name",
+            vec![LabeledStatus {
+                severity: Some(Severity::Minor),
+                latest_version: Some("1.5.0"),
+                unconstrained_latest_version: Some("2.0.0"),
+                ..labeled_status(&labels, CheckStatus::UpgradeAvailable)
+            }],
+        )];
+        metrics.update(check_results.into_iter());
+
+        let lines = encode_lines(&metrics, Registry::default());
+        for expected in [
+            "upgrades{status=\"upgrade-available\",name=\"check_name\",label=\"label-value\"} 1",
+            "upgrades_latest_version_info{name=\"check_name\",latest_version=\"1.5.0\",unconstrained_latest_version=\"2.0.0\",severity=\"minor\",label=\"label-value\"} 1",
+        ] {
+            assert!(lines.contains(&expected.into()), "{expected} in {lines:#?}");
+        }
+    }
+
+    #[test]
+    fn test_update_keeps_unchanged_series() {
+        let metrics = Metrics::new(MetricsConfig::default());
+        let (a, b) = (
+            HashMap::from([("label".into(), "a".into())]),
+            HashMap::from([("label".into(), "b".into())]),
+        );
+        let series = |label: &str| {
+            format!("upgrades{{status=\"up-to-date\",name=\"check_name\",label=\"{label}\"}} 1")
+        };
+
+        metrics.update(
+            [(
+                "check_name",
+                vec![
+                    labeled_status(&a, CheckStatus::UpToDate),
+                    labeled_status(&b, CheckStatus::UpToDate),
+                ],
+            )]
+            .into_iter(),
+        );
+        let lines = encode_lines(&metrics, Registry::default());
+        assert!(lines.contains(&series("a")), "{lines:#?}");
+        assert!(lines.contains(&series("b")), "{lines:#?}");
+
+        metrics.update(
+            [(
+                "check_name",
+                vec![labeled_status(&a, CheckStatus::UpToDate)],
+            )]
+            .into_iter(),
+        );
+        let lines = encode_lines(&metrics, Registry::default());
+        assert!(lines.contains(&series("a")), "{lines:#?}");
+        assert!(!lines.contains(&series("b")), "{lines:#?}");
+        assert!(lines
+            .contains(&"upgrades_by_status{name=\"check_name\",status=\"up-to-date\"} 1".into()));
+    }
+
+    #[test]
+    fn test_prefix_and_const_labels() {
+        let config: MetricsConfig = serde_yaml::from_str(
+            r#"
+            prefix: release_exporter
+            const_labels:
+              site: eu1
+            "#,
+        )
+        .unwrap();
+        let metrics = Metrics::new(config.clone());
+        let labels = HashMap::from([("app.kubernetes.io/name".into(), "app".into())]);
+        let versions = [VersionInfo {
+            version: Some("1.2.3".into()),
+            labels,
+            metadata: ReleaseMetadata::default(),
+        }];
+        metrics
+            .update_versions([("provider_name", VersionScheme::Semver, &versions[..])].into_iter());
+        metrics.update([("check_name", vec![])].into_iter());
+
+        let lines = encode_lines(&metrics, config.registry());
+        for expected in [
+            "release_info{site=\"eu1\",provider=\"provider_name\",version=\"1.2.3\",app_kubernetes_io_name=\"app\"} 1",
+            "release_cache_entries{site=\"eu1\"} 0",
+            "release_exporter_upgrades_by_status{site=\"eu1\",name=\"check_name\",status=\"unknown\"} 0",
+        ] {
+            assert!(lines.contains(&expected.into()), "{expected} in {lines:#?}");
+        }
+    }
+
+    #[test]
+    fn test_label_conflicts() {
+        let config: MetricsConfig = serde_yaml::from_str("const_labels: {site: eu1}").unwrap();
+        let metrics = Metrics::new(config.clone());
+        let labels = HashMap::from([
+            ("name".into(), "provider-name".into()),
+            ("exported_name".into(), "exported".into()),
+            ("status".into(), "running".into()),
+            ("site".into(), "us1".into()),
+            ("a.b".into(), "sanitized".into()),
+            ("a_b".into(), "valid".into()),
+        ]);
+        let check_results = [(
+            "check_name",
+            vec![labeled_status(&labels, CheckStatus::UpToDate)],
+        )];
+        metrics.update(check_results.into_iter());
+
+        let lines = encode_lines(&metrics, config.registry());
+        let expected = "upgrades{site=\"eu1\",status=\"up-to-date\",name=\"check_name\",\
+            a_b=\"valid\",\
+            exported_name=\"exported\",\
+            exported_exported_name=\"provider-name\",\
+            exported_site=\"us1\",\
+            exported_status=\"running\",\
+            exported_a_b=\"sanitized\"} 1";
+        assert!(lines.contains(&expected.into()), "{expected} in {lines:#?}");
+    }
+
+    #[test]
+    fn test_label_conflicts_apply_to_all_metrics() {
+        let metrics = Metrics::new(MetricsConfig::default());
+        let labels = HashMap::from([("version".into(), "1.0.0".into())]);
+        metrics.update(
+            [(
+                "check_name",
+                vec![labeled_status(&labels, CheckStatus::UpToDate)],
+            )]
+            .into_iter(),
+        );
+
+        // Renamed although `upgrades` has no built-in `version` label.
+        let lines = encode_lines(&metrics, Registry::default());
+        let expected =
+            "upgrades{status=\"up-to-date\",name=\"check_name\",exported_version=\"1.0.0\"} 1";
+        assert!(lines.contains(&expected.into()), "{expected} in {lines:#?}");
+    }
+
+    #[test]
+    fn test_invalid_metrics_config() {
+        for (config, expected_error) in [
+            ("prefix: 1st", "invalid metric name prefix: 1st"),
+            (
+                "const_labels: {site.name: eu1}",
+                "invalid constant label name: site.name",
+            ),
+            (
+                "const_labels: {__site: eu1}",
+                "invalid constant label name: __site",
+            ),
+            (
+                "const_labels: {name: eu1}",
+                "constant label conflicts with built-in label: name",
+            ),
+        ] {
+            let error = serde_yaml::from_str::<MetricsConfig>(config).unwrap_err();
+            assert!(
+                error.to_string().contains(expected_error),
+                "{expected_error} in {error}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_sanitize_label_name() {
+        assert_eq!(sanitize_label_name("valid_name1"), "valid_name1");
+        assert_eq!(
+            sanitize_label_name("app.kubernetes.io/name"),
+            "app_kubernetes_io_name"
+        );
+        assert_eq!(sanitize_label_name("1st"), "_1st");
+        assert_eq!(sanitize_label_name(""), "_");
+    }
+
+    #[test]
+    fn test_upgrade_status_metrics() {
+        let metrics = Metrics::new(MetricsConfig::default());
+        let labels = |value: &str| HashMap::from([("label".into(), value.into())]);
+        let (a, b, c) = (labels("a"), labels("b"), labels("c"));
+        let check_results = [
+            (
+                "check_name",
+                vec![
+                    labeled_status(&a, CheckStatus::UpgradeAvailable),
+                    labeled_status(&b, CheckStatus::UpToDate),
+                    labeled_status(&c, CheckStatus::Unknown),
+                ],
+            ),
+            ("no_versions", vec![]),
+        ];
+        metrics.update(check_results.into_iter());
+
+        let mut lines: Vec<String> = encode_lines(&metrics, Registry::default())
+            .into_iter()
+            .filter(|line| {
+                line.starts_with("upgrade_available") || line.starts_with("upgrades_by_status")
+            })
+            .collect();
+        lines.sort();
+        assert_eq!(
+            lines,
+            vec![
+                "upgrade_available{name=\"check_name\",label=\"a\"} 1.0",
+                "upgrade_available{name=\"check_name\",label=\"b\"} 0.0",
+                "upgrade_available{name=\"check_name\",label=\"c\"} NaN",
+                "upgrades_by_status{name=\"check_name\",status=\"ahead-of-latest\"} 0",
+                "upgrades_by_status{name=\"check_name\",status=\"incomparable\"} 0",
+                "upgrades_by_status{name=\"check_name\",status=\"unknown\"} 1",
+                "upgrades_by_status{name=\"check_name\",status=\"up-to-date\"} 1",
+                "upgrades_by_status{name=\"check_name\",status=\"upgrade-available\"} 1",
+                "upgrades_by_status{name=\"no_versions\",status=\"ahead-of-latest\"} 0",
+                "upgrades_by_status{name=\"no_versions\",status=\"incomparable\"} 0",
+                "upgrades_by_status{name=\"no_versions\",status=\"unknown\"} 0",
+                "upgrades_by_status{name=\"no_versions\",status=\"up-to-date\"} 0",
+                "upgrades_by_status{name=\"no_versions\",status=\"upgrade-available\"} 0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_update_version_metrics() {
+        let metrics = Metrics::new(MetricsConfig::default());
+        let version = |version: Option<&str>| VersionInfo {
+            version: version.map(String::from),
+            labels: HashMap::new(),
+            metadata: ReleaseMetadata::default(),
+        };
+        let versions = [
+            version(None),
+            version(Some("v1.12-rc1")),
+            version(Some("1.11.0")),
+        ];
+        let debian_versions = [version(Some("1:2.3-1"))];
+        metrics.update_versions(
+            [
+                ("provider_name", VersionScheme::Semver, &versions[..]),
+                (
+                    "debian_provider",
+                    VersionScheme::Debian,
+                    &debian_versions[..],
+                ),
+            ]
+            .into_iter(),
+        );
+
+        let mut lines: Vec<String> = encode_lines(&metrics, Registry::default())
+            .into_iter()
+            .filter(|line| line.starts_with("release_"))
+            .collect();
+        lines.sort();
+        assert_eq!(
+            lines,
+            vec![
+                "release_cache_entries 0",
+                "release_info{provider=\"debian_provider\",version=\"1:2.3-1\"} 1",
+                "release_info{provider=\"provider_name\",version=\"1.11.0\"} 1",
+                "release_info{provider=\"provider_name\",version=\"v1.12-rc1\"} 1",
+                "release_version_component{provider=\"debian_provider\",component=\"major\"} 2",
+                "release_version_component{provider=\"debian_provider\",component=\"minor\"} 3",
+                "release_version_component{provider=\"provider_name\",component=\"major\"} 1",
+                "release_version_component{provider=\"provider_name\",component=\"minor\"} 12",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_observe_provider_fetch() {
+        let metrics = Metrics::new(MetricsConfig::default());
+        metrics.observe_provider_fetch("provider_name", Duration::from_millis(80), None);
+        metrics.observe_provider_fetch(
+            "provider_name",
+            Duration::from_millis(20),
+            Some("http_status"),
+        );
+
+        let lines = encode_lines(&metrics, Registry::default());
+        for expected in [
+            "release_provider_up{provider=\"provider_name\"} 0",
+            "release_provider_errors_total{provider=\"provider_name\",error=\"http_status\"} 1",
+            "release_provider_fetch_duration_seconds_count{provider=\"provider_name\"} 2",
+            "release_provider_fetch_duration_seconds_bucket{le=\"0.05\",provider=\"provider_name\"} 1",
+        ] {
+            assert!(lines.contains(&expected.into()), "{expected} in {lines:#?}");
+        }
+        assert!(lines.iter().any(|line| line.starts_with(
+            "release_provider_last_success_timestamp_seconds{provider=\"provider_name\"} 1"
+        )));
+    }
+
+    #[test]
+    fn test_update_cache() {
+        let metrics = Metrics::new(MetricsConfig::default());
+        metrics.observe_cache_lookup("provider_name", false);
+        metrics.observe_cache_lookup("provider_name", true);
+        metrics.observe_cache_lookup("provider_name", true);
+        let expiry = UNIX_EPOCH + Duration::from_secs(1700000000);
+        metrics.update_cache([("provider_name", &expiry)].into_iter());
+
+        let lines = encode_lines(&metrics, Registry::default());
+        for expected in [
+            "release_cache_hits_total{provider=\"provider_name\"} 2",
+            "release_cache_misses_total{provider=\"provider_name\"} 1",
+            "release_cache_entries 1",
+            "release_cache_expiry_timestamp_seconds{provider=\"provider_name\"} 1700000000",
+        ] {
+            assert!(lines.contains(&expected.into()), "{expected} in {lines:#?}");
+        }
+    }
 }
@@ -0,0 +1,131 @@
+use std::{borrow::Cow, sync::OnceLock};
+
+use regex::Regex;
+use serde::Deserialize;
+
+fn prerelease_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(
+            r"(?i)^(?P<release>v?[0-9]+(?:\.[0-9]+)*)[-_.]?(?P<label>alpha|a|beta|b|rc|c|pre|preview|dev)[-_.]?(?P<number>[0-9]+)?(?P<rest>[-+.].*)?$",
+        )
+        .unwrap()
+    })
+}
+
+/// A step to normalize versions before they are compared.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NormalizationStep {
+    /// `1.0-RC1` becomes `1.0-rc1`.
+    Lowercase,
+    /// `1.0.0+build.5` becomes `1.0.0`.
+    StripBuildMetadata,
+    /// `1.2` becomes `1.2.0`.
+    PadRelease,
+    /// `1.0rc1`, `1.0-RC-1`, and `1.0.rc.1` become `1.0-rc.1`.
+    UnifyPrerelease,
+}
+
+impl NormalizationStep {
+    fn apply(&self, version: &str) -> String {
+        match self {
+            NormalizationStep::Lowercase => version.to_lowercase(),
+            NormalizationStep::StripBuildMetadata => version
+                .split_once('+')
+                .map_or(version, |(version, _)| version)
+                .into(),
+            NormalizationStep::PadRelease => {
+                let start = usize::from(version.starts_with(['v', 'V']));
+                let end = version[start..]
+                    .find(|c: char| !c.is_ascii_digit() && c != '.')
+                    .map_or(version.len(), |end| start + end);
+                let release = &version[start..end];
+                if release.is_empty()
+                    || release.split('.').any(str::is_empty)
+                    || release.split('.').count() >= 3
+                {
+                    return version.into();
+                }
+                let padding = ".0".repeat(3 - release.split('.').count());
+                format!("{}{}{}", &version[..end], padding, &version[end..])
+            }
+            NormalizationStep::UnifyPrerelease => {
+                let Some(captures) = prerelease_regex().captures(version) else {
+                    return version.into();
+                };
+                let label = match captures["label"].to_lowercase().as_str() {
+                    "a" => "alpha".into(),
+                    "b" => "beta".into(),
+                    "c" => "rc".into(),
+                    label => label.to_owned(),
+                };
+                let mut normalized = format!("{}-{}", &captures["release"], label);
+                if let Some(number) = captures.name("number") {
+                    normalized.push('.');
+                    normalized.push_str(number.as_str());
+                }
+                if let Some(rest) = captures.name("rest") {
+                    normalized.push_str(rest.as_str());
+                }
+                normalized
+            }
+        }
+    }
+}
+
+/// Normalization steps applied in order.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct Normalization(Vec<NormalizationStep>);
+
+impl Normalization {
+    pub fn apply<'a>(&self, version: &'a str) -> Cow<'a, str> {
+        self.0.iter().fold(Cow::Borrowed(version), |version, step| {
+            Cow::Owned(step.apply(&version))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Normalization, NormalizationStep};
+
+    #[test]
+    fn test_normalization_steps() {
+        for (step, version, expected) in [
+            (NormalizationStep::Lowercase, "1.0-RC1", "1.0-rc1"),
+            (NormalizationStep::StripBuildMetadata, "1.0.0+b.5", "1.0.0"),
+            (NormalizationStep::StripBuildMetadata, "1.0.0", "1.0.0"),
+            (NormalizationStep::PadRelease, "1.2", "1.2.0"),
+            (NormalizationStep::PadRelease, "v1-rc1", "v1.0.0-rc1"),
+            (NormalizationStep::PadRelease, "1.2.3.4", "1.2.3.4"),
+            (NormalizationStep::PadRelease, "latest", "latest"),
+            (NormalizationStep::UnifyPrerelease, "1.0rc1", "1.0-rc.1"),
+            (NormalizationStep::UnifyPrerelease, "1.0-RC-1", "1.0-rc.1"),
+            (
+                NormalizationStep::UnifyPrerelease,
+                "1.0.0-rc.1",
+                "1.0.0-rc.1",
+            ),
+            (
+                NormalizationStep::UnifyPrerelease,
+                "2.0b3+x",
+                "2.0-beta.3+x",
+            ),
+            (NormalizationStep::UnifyPrerelease, "1.0.0", "1.0.0"),
+        ] {
+            assert_eq!(step.apply(version), expected, "{step:?} {version}");
+        }
+    }
+
+    #[test]
+    fn test_deserialize_normalization() {
+        let normalization: Normalization = serde_yaml::from_str(
+            "[lowercase, strip_build_metadata, pad_release, unify_prerelease]",
+        )
+        .unwrap();
+        assert_eq!(normalization.apply("V1.2RC1+Build"), "v1.2.0-rc.1");
+        assert_eq!(Normalization::default().apply("1.2+b"), "1.2+b");
+    }
+}
@@ -1,14 +1,20 @@
 use std::collections::HashMap;
 use std::time::Duration;
 
-use serde::{de::Visitor, Deserialize, Deserializer};
+use chrono::{DateTime, Utc};
+use serde::{de::Visitor, Deserialize, Deserializer, Serialize};
 
-use self::github::LatestReleaseProvider;
+use crate::version_scheme::VersionScheme;
+
+use self::{github::LatestReleaseProvider, version_extractor::VersionExtractor};
 
 pub mod error;
 pub mod github;
+pub mod http_config;
 pub mod prometheus;
+pub mod static_versions;
 pub mod version_extractor;
+pub mod version_template;
 
 struct DurationSecsVisitor;
 
@@ -34,10 +40,19 @@ where
     deserializer.deserialize_u64(DurationSecsVisitor)
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
 pub struct VersionInfo {
     pub version: Option<String>,
     pub labels: HashMap<String, String>,
+    pub metadata: ReleaseMetadata,
+}
+
+/// Additional information about a release, if provided by the provider.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ReleaseMetadata {
+    pub published_at: Option<DateTime<Utc>>,
+    pub url: Option<String>,
+    pub prerelease: Option<bool>,
 }
 
 fn default_github_cache_duration() -> Duration {
@@ -58,9 +73,31 @@ pub enum Provider {
         )]
         cache_duration: Duration,
     },
+    GithubReleases {
+        #[serde(flatten)]
+        config: github::ReleasesProvider,
+        name: String,
+        #[serde(
+            rename = "cache_seconds",
+            deserialize_with = "deserialize_duration_secs",
+            default = "default_github_cache_duration"
+        )]
+        cache_duration: Duration,
+    },
     Prometheus {
         #[serde(flatten)]
-        config: prometheus::Provider,
+        config: Box<prometheus::Provider>,
+        name: String,
+        #[serde(
+            rename = "cache_seconds",
+            deserialize_with = "deserialize_duration_secs",
+            default = "Duration::default"
+        )]
+        cache_duration: Duration,
+    },
+    Static {
+        #[serde(flatten)]
+        config: static_versions::Provider,
         name: String,
         #[serde(
             rename = "cache_seconds",
@@ -79,11 +116,21 @@ impl Provider {
                 name,
                 cache_duration: _,
             } => name,
+            Provider::GithubReleases {
+                config: _,
+                name,
+                cache_duration: _,
+            } => name,
             Provider::Prometheus {
                 config: _,
                 name,
                 cache_duration: _,
             } => name,
+            Provider::Static {
+                config: _,
+                name,
+                cache_duration: _,
+            } => name,
         }
     }
 
@@ -94,11 +141,85 @@ impl Provider {
                 name: _,
                 cache_duration,
             } => cache_duration,
+            Provider::GithubReleases {
+                config: _,
+                name: _,
+                cache_duration,
+            } => cache_duration,
             Provider::Prometheus {
                 config: _,
                 name: _,
                 cache_duration,
             } => cache_duration,
+            Provider::Static {
+                config: _,
+                name: _,
+                cache_duration,
+            } => cache_duration,
+        }
+    }
+
+    pub fn version_scheme(&self) -> VersionScheme {
+        match self {
+            Provider::LatestGithubRelease {
+                config,
+                name: _,
+                cache_duration: _,
+            } => config.version_scheme,
+            Provider::GithubReleases {
+                config,
+                name: _,
+                cache_duration: _,
+            } => config.version_scheme,
+            Provider::Prometheus {
+                config,
+                name: _,
+                cache_duration: _,
+            } => config.version_scheme,
+            Provider::Static {
+                config,
+                name: _,
+                cache_duration: _,
+            } => config.version_scheme,
+        }
+    }
+
+    pub fn version_extractor_mut(&mut self) -> Option<&mut VersionExtractor> {
+        match self {
+            Provider::LatestGithubRelease {
+                config,
+                name: _,
+                cache_duration: _,
+            } => Some(&mut config.version_extractor),
+            Provider::GithubReleases {
+                config,
+                name: _,
+                cache_duration: _,
+            } => Some(&mut config.version_extractor),
+            Provider::Prometheus {
+                config,
+                name: _,
+                cache_duration: _,
+            } => Some(&mut config.version_extractor),
+            Provider::Static {
+                config: _,
+                name: _,
+                cache_duration: _,
+            } => None,
+        }
+    }
+
+    pub fn configure_http_client<F>(&mut self, client_builder: F) -> error::Result<()>
+    where
+        F: Fn() -> reqwest::ClientBuilder,
+    {
+        match self {
+            Provider::Prometheus {
+                config,
+                name: _,
+                cache_duration: _,
+            } => config.configure_http_client(client_builder),
+            _ => Ok(()),
         }
     }
 
@@ -114,11 +235,26 @@ impl Provider {
             } => {
                 vec![config.fetch(http_client).await?.into()]
             }
+            Provider::GithubReleases {
+                config,
+                name: _,
+                cache_duration: _,
+            } => config
+                .fetch(http_client)
+                .await?
+                .into_iter()
+                .map(VersionInfo::from)
+                .collect(),
             Provider::Prometheus {
                 config,
                 name: _,
                 cache_duration: _,
             } => config.fetch(http_client).await?,
+            Provider::Static {
+                config,
+                name: _,
+                cache_duration: _,
+            } => config.fetch(),
         })
     }
 }
@@ -1,9 +1,55 @@
+#[allow(clippy::enum_variant_names)]
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error("error during network communication: {source}")]
     TransportError { source: reqwest::Error },
     #[error("invalid url: {source}")]
     InvalidUrl { source: url::ParseError },
+    #[error("failed to read {path}: {source}")]
+    FileRead {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+    #[error("unexpected HTTP status: {status}")]
+    HttpStatus { status: reqwest::StatusCode },
+    #[error("Prometheus query failed with {error_type}: {error}")]
+    PrometheusQuery { error_type: String, error: String },
+    #[error("Prometheus query returned warnings: {}", warnings.join("; "))]
+    PrometheusWarnings { warnings: Vec<String> },
+    #[error("invalid http_config: {reason}")]
+    InvalidHttpConfig { reason: String },
+    #[error("invalid certificate or key in {path}: {source}")]
+    InvalidCertificate {
+        path: std::path::PathBuf,
+        source: reqwest::Error,
+    },
+}
+
+impl Error {
+    /// A short classification of the error, e.g. for metric labels.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Error::TransportError { source } if source.status().is_some() => "http_status",
+            Error::TransportError { source } if source.is_decode() => "decode",
+            Error::TransportError { source } if source.is_timeout() => "timeout",
+            Error::TransportError { source: _ } => "transport",
+            Error::InvalidUrl { source: _ } => "invalid_url",
+            Error::FileRead { path: _, source: _ } => "file_read",
+            Error::HttpStatus { status: _ } => "http_status",
+            Error::PrometheusQuery {
+                error_type: _,
+                error: _,
+            } => "prometheus_query",
+            Error::PrometheusWarnings { warnings: _ } => "prometheus_warnings",
+            Error::InvalidHttpConfig { reason: _ } => "invalid_config",
+            Error::InvalidCertificate { path: _, source: _ } => "invalid_config",
+        }
+    }
+}
+
+/// Classifies an error returned by a provider, see [`Error::kind`].
+pub fn kind(error: &(dyn std::error::Error + 'static)) -> &'static str {
+    error.downcast_ref::<Error>().map_or("other", Error::kind)
 }
 
 impl From<reqwest::Error> for Error {
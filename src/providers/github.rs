@@ -1,13 +1,14 @@
 use std::collections::HashMap;
 
+use chrono::{DateTime, Utc};
 use serde::{
     de::{Unexpected, Visitor},
     Deserialize, Deserializer,
 };
 
-use crate::baseurl::BaseUrl;
+use crate::{baseurl::BaseUrl, version_scheme::VersionScheme};
 
-use super::{version_extractor::VersionExtractor, VersionInfo};
+use super::{version_extractor::VersionExtractor, ReleaseMetadata, VersionInfo};
 
 fn github_api_url() -> BaseUrl {
     BaseUrl::parse("https://api.github.com").unwrap()
@@ -16,6 +17,7 @@ fn github_api_url() -> BaseUrl {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct GithubRelease {
     version: Option<String>,
+    metadata: ReleaseMetadata,
 }
 
 impl From<GithubRelease> for VersionInfo {
@@ -23,6 +25,7 @@ impl From<GithubRelease> for VersionInfo {
         Self {
             version: release.version,
             labels: HashMap::new(),
+            metadata: release.metadata,
         }
     }
 }
@@ -34,6 +37,26 @@ pub struct LatestReleaseProvider {
     #[serde(flatten)]
     pub version_extractor: VersionExtractor,
 
+    #[serde(default)]
+    pub version_scheme: VersionScheme,
+
+    #[serde(default = "github_api_url")]
+    pub api_url: BaseUrl,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ReleasesProvider {
+    pub repo: GithubRepo,
+
+    #[serde(flatten)]
+    pub version_extractor: VersionExtractor,
+
+    #[serde(default)]
+    pub version_scheme: VersionScheme,
+
+    #[serde(default)]
+    pub include_prereleases: bool,
+
     #[serde(default = "github_api_url")]
     pub api_url: BaseUrl,
 }
@@ -77,8 +100,34 @@ impl<'de> Visitor<'de> for GithubRepoVisitor {
 }
 
 #[derive(Clone, Debug, Deserialize)]
-pub struct LatestReleaseResponse {
+struct ReleaseResponse {
     tag_name: String,
+    html_url: Option<String>,
+    published_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    prerelease: bool,
+}
+
+impl ReleaseResponse {
+    fn into_release(self, version_extractor: &VersionExtractor) -> GithubRelease {
+        GithubRelease {
+            version: version_extractor.extract(&self.tag_name),
+            metadata: ReleaseMetadata {
+                published_at: self.published_at,
+                url: self.html_url,
+                prerelease: Some(self.prerelease),
+            },
+        }
+    }
+}
+
+fn github_request(http_client: &reqwest::Client, url: BaseUrl) -> reqwest::RequestBuilder {
+    http_client
+        .get(url.into_url())
+        .header("Accept", "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", "2022-11-28")
 }
 
 impl LatestReleaseProvider {
@@ -95,17 +144,37 @@ impl LatestReleaseProvider {
             "latest",
         ]);
 
-        let api_response: LatestReleaseResponse = http_client
-            .get(url.into_url())
-            .header("Accept", "application/vnd.github+json")
-            .header("X-GitHub-Api-Version", "2022-11-28")
+        let api_response: ReleaseResponse = github_request(http_client, url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(api_response.into_release(&self.version_extractor))
+    }
+}
+
+impl ReleasesProvider {
+    /// Fetches the up to 100 most recent releases, excluding drafts.
+    pub async fn fetch(
+        &self,
+        http_client: &reqwest::Client,
+    ) -> super::error::Result<Vec<GithubRelease>> {
+        let mut url = self.api_url.clone();
+        url.extend(["repos", &self.repo.user, &self.repo.name, "releases"]);
+        url.query_pairs_mut().append_pair("per_page", "100");
+
+        let api_response: Vec<ReleaseResponse> = github_request(http_client, url)
             .send()
             .await?
             .error_for_status()?
             .json()
             .await?;
-        let version = self.version_extractor.extract(&api_response.tag_name);
-        Ok(GithubRelease { version })
+        Ok(api_response
+            .into_iter()
+            .filter(|release| !release.draft && (self.include_prereleases || !release.prerelease))
+            .map(|release| release.into_release(&self.version_extractor))
+            .collect())
     }
 }
 
@@ -114,11 +183,12 @@ mod tests {
     use serde_test::{assert_de_tokens, assert_de_tokens_error, Token};
 
     use crate::{
-        providers::{github::GithubRelease, version_extractor::VersionExtractor},
+        providers::{github::GithubRelease, version_extractor::VersionExtractor, ReleaseMetadata},
         test_config::github_api_url,
+        version_scheme::VersionScheme,
     };
 
-    use super::{GithubRepo, LatestReleaseProvider};
+    use super::{GithubRepo, LatestReleaseProvider, ReleasesProvider};
 
     #[tokio::test]
     async fn test_fetch_latest_github_release() {
@@ -130,16 +200,53 @@ mod tests {
             },
             api_url: github_api_url(),
             version_extractor: VersionExtractor::default(),
+            version_scheme: VersionScheme::default(),
         };
         let release = provider.fetch(&client).await.unwrap();
         assert_eq!(
             release,
             GithubRelease {
                 version: Some("0.8.0".into()),
+                metadata: ReleaseMetadata {
+                    published_at: Some("2022-12-11T18:18:00Z".parse().unwrap()),
+                    url: Some(
+                        "https://github.com/jgosmann/dmarc-metrics-exporter/releases/tag/v0.8.0"
+                            .into()
+                    ),
+                    prerelease: Some(false),
+                },
             }
         )
     }
 
+    #[tokio::test]
+    async fn test_fetch_github_releases() {
+        let client = reqwest::Client::new();
+        let mut provider = ReleasesProvider {
+            repo: GithubRepo {
+                user: "jgosmann".into(),
+                name: "dmarc-metrics-exporter".into(),
+            },
+            api_url: github_api_url(),
+            version_extractor: VersionExtractor::default(),
+            version_scheme: VersionScheme::default(),
+            include_prereleases: false,
+        };
+        let versions = |releases: Vec<GithubRelease>| -> Vec<String> {
+            releases.into_iter().filter_map(|r| r.version).collect()
+        };
+        assert_eq!(
+            versions(provider.fetch(&client).await.unwrap()),
+            vec!["0.8.0", "0.7.0", "0.6.2", "0.6.1"]
+        );
+
+        provider.include_prereleases = true;
+        assert_eq!(
+            versions(provider.fetch(&client).await.unwrap()),
+            vec!["0.8.0", "0.8.0-rc1", "0.7.0", "0.6.2", "0.6.1"]
+        );
+    }
+
     #[test]
     fn test_deserialize_github_repo() {
         let repo = GithubRepo {
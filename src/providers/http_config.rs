@@ -0,0 +1,332 @@
+use std::{collections::HashMap, path::Path, path::PathBuf};
+
+use reqwest::{Certificate, ClientBuilder, Identity, Proxy, RequestBuilder};
+use serde::Deserialize;
+use url::Url;
+
+use super::error::{Error, Result};
+
+fn default_authorization_type() -> String {
+    "Bearer".into()
+}
+
+/// HTTP client settings following the schema of Prometheus' `http_config`.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct HttpConfig {
+    pub basic_auth: Option<BasicAuth>,
+    pub authorization: Option<Authorization>,
+    pub tls_config: Option<TlsConfig>,
+    pub proxy_url: Option<Url>,
+
+    #[serde(default)]
+    pub http_headers: HashMap<String, HttpHeader>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct BasicAuth {
+    pub username: String,
+    pub password: Option<String>,
+    pub password_file: Option<PathBuf>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Authorization {
+    #[serde(rename = "type", default = "default_authorization_type")]
+    pub auth_type: String,
+    pub credentials: Option<String>,
+    pub credentials_file: Option<PathBuf>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct TlsConfig {
+    pub ca_file: Option<PathBuf>,
+    pub cert_file: Option<PathBuf>,
+    pub key_file: Option<PathBuf>,
+
+    #[serde(default)]
+    pub insecure_skip_verify: bool,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct HttpHeader {
+    #[serde(default)]
+    pub values: Vec<String>,
+    #[serde(default)]
+    pub secrets: Vec<String>,
+    #[serde(default)]
+    pub files: Vec<PathBuf>,
+}
+
+/// Reads a secret from a file. Like Prometheus, surrounding whitespace is
+/// stripped. The file is read on every call so that rotated secrets are
+/// picked up without a restart.
+fn read_secret(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path)
+        .map(|secret| secret.trim().to_owned())
+        .map_err(|source| Error::FileRead {
+            path: path.to_owned(),
+            source,
+        })
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>> {
+    std::fs::read(path).map_err(|source| Error::FileRead {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+impl HttpConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.basic_auth.is_some() && self.authorization.is_some() {
+            return Err(Error::InvalidHttpConfig {
+                reason: "at most one of basic_auth and authorization may be configured".into(),
+            });
+        }
+        if let Some(basic_auth) = &self.basic_auth {
+            if basic_auth.password.is_some() && basic_auth.password_file.is_some() {
+                return Err(Error::InvalidHttpConfig {
+                    reason:
+                        "at most one of basic_auth password and password_file may be configured"
+                            .into(),
+                });
+            }
+        }
+        if let Some(authorization) = &self.authorization {
+            if authorization.credentials.is_some() && authorization.credentials_file.is_some() {
+                return Err(Error::InvalidHttpConfig {
+                    reason: "at most one of authorization credentials and credentials_file may be configured".into(),
+                });
+            }
+            if authorization.auth_type.eq_ignore_ascii_case("basic") {
+                return Err(Error::InvalidHttpConfig {
+                    reason: "authorization type cannot be set to Basic, use basic_auth instead"
+                        .into(),
+                });
+            }
+        }
+        if let Some(tls_config) = &self.tls_config {
+            if tls_config.cert_file.is_some() != tls_config.key_file.is_some() {
+                return Err(Error::InvalidHttpConfig {
+                    reason: "tls_config cert_file and key_file must be configured together".into(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether a dedicated HTTP client is required because the connection
+    /// settings differ from the shared default client.
+    pub fn requires_dedicated_client(&self) -> bool {
+        self.tls_config.is_some() || self.proxy_url.is_some()
+    }
+
+    pub fn configure_client(&self, mut builder: ClientBuilder) -> Result<ClientBuilder> {
+        if let Some(tls_config) = &self.tls_config {
+            if let Some(ca_file) = &tls_config.ca_file {
+                let certificates =
+                    Certificate::from_pem_bundle(&read_file(ca_file)?).map_err(|source| {
+                        Error::InvalidCertificate {
+                            path: ca_file.clone(),
+                            source,
+                        }
+                    })?;
+                for certificate in certificates {
+                    builder = builder.add_root_certificate(certificate);
+                }
+            }
+            if let (Some(cert_file), Some(key_file)) = (&tls_config.cert_file, &tls_config.key_file)
+            {
+                let identity =
+                    Identity::from_pkcs8_pem(&read_file(cert_file)?, &read_file(key_file)?)
+                        .map_err(|source| Error::InvalidCertificate {
+                            path: cert_file.clone(),
+                            source,
+                        })?;
+                builder = builder.identity(identity);
+            }
+            builder = builder.danger_accept_invalid_certs(tls_config.insecure_skip_verify);
+        }
+        if let Some(proxy_url) = &self.proxy_url {
+            builder = builder.proxy(Proxy::all(proxy_url.clone())?);
+        }
+        Ok(builder)
+    }
+
+    pub fn apply(&self, mut request: RequestBuilder) -> Result<RequestBuilder> {
+        if let Some(basic_auth) = &self.basic_auth {
+            let password = match &basic_auth.password_file {
+                Some(password_file) => Some(read_secret(password_file)?),
+                None => basic_auth.password.clone(),
+            };
+            request = request.basic_auth(&basic_auth.username, password);
+        }
+        if let Some(authorization) = &self.authorization {
+            let credentials = match &authorization.credentials_file {
+                Some(credentials_file) => read_secret(credentials_file)?,
+                None => authorization.credentials.clone().unwrap_or_default(),
+            };
+            request = request.header(
+                reqwest::header::AUTHORIZATION,
+                format!("{} {}", authorization.auth_type, credentials),
+            );
+        }
+        for (name, header) in &self.http_headers {
+            for value in header.values.iter().chain(&header.secrets) {
+                request = request.header(name, value);
+            }
+            for file in &header.files {
+                request = request.header(name, read_secret(file)?);
+            }
+        }
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::AUTHORIZATION;
+
+    use crate::test_config::TempFile;
+
+    use super::{Authorization, BasicAuth, Error, HttpConfig, HttpHeader, TlsConfig};
+
+    fn build_request(http_config: &HttpConfig) -> reqwest::Request {
+        http_config
+            .apply(reqwest::Client::new().get("http://localhost/"))
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_deserialize_http_config() {
+        let http_config: HttpConfig = serde_yaml::from_str(
+            r#"
+            basic_auth:
+              username: user
+              password: secret
+            tls_config:
+              ca_file: /etc/ssl/ca.pem
+              insecure_skip_verify: true
+            proxy_url: http://proxy:3128
+            http_headers:
+              X-Scope-OrgID:
+                values: [tenant-1]
+            "#,
+        )
+        .unwrap();
+        assert!(http_config.validate().is_ok());
+        assert!(http_config.requires_dedicated_client());
+        assert_eq!(http_config.basic_auth.unwrap().username, "user");
+        assert_eq!(
+            http_config.tls_config.unwrap().ca_file,
+            Some("/etc/ssl/ca.pem".into())
+        );
+        assert_eq!(
+            http_config.http_headers["X-Scope-OrgID"].values,
+            vec![String::from("tenant-1")]
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_multiple_auth_methods() {
+        let http_config = HttpConfig {
+            basic_auth: Some(BasicAuth {
+                username: "user".into(),
+                password: None,
+                password_file: None,
+            }),
+            authorization: Some(Authorization {
+                auth_type: "Bearer".into(),
+                credentials: Some("token".into()),
+                credentials_file: None,
+            }),
+            ..Default::default()
+        };
+        assert!(http_config.validate().is_err());
+    }
+
+    #[test]
+    fn test_configure_client_rejects_invalid_ca_file() {
+        let ca_file = TempFile::new(
+            "-----BEGIN CERTIFICATE-----\nnot a certificate\n-----END CERTIFICATE-----\n",
+        );
+        let http_config = HttpConfig {
+            tls_config: Some(TlsConfig {
+                ca_file: Some(ca_file.path().into()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let error = http_config
+            .configure_client(reqwest::Client::builder())
+            .unwrap_err();
+        assert!(
+            matches!(&error, Error::InvalidCertificate { path, source: _ } if path == ca_file.path())
+        );
+        assert_eq!(error.kind(), "invalid_config");
+    }
+
+    #[test]
+    fn test_apply_basic_auth() {
+        let http_config = HttpConfig {
+            basic_auth: Some(BasicAuth {
+                username: "user".into(),
+                password: Some("secret".into()),
+                password_file: None,
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            build_request(&http_config).headers()[AUTHORIZATION],
+            "Basic dXNlcjpzZWNyZXQ="
+        );
+    }
+
+    #[test]
+    fn test_apply_authorization_rereads_credentials_file() {
+        let credentials_file = TempFile::new("first-token\n");
+        let http_config = HttpConfig {
+            authorization: Some(Authorization {
+                auth_type: "Bearer".into(),
+                credentials: None,
+                credentials_file: Some(credentials_file.path().into()),
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            build_request(&http_config).headers()[AUTHORIZATION],
+            "Bearer first-token"
+        );
+
+        credentials_file.write("second-token\n");
+        assert_eq!(
+            build_request(&http_config).headers()[AUTHORIZATION],
+            "Bearer second-token"
+        );
+    }
+
+    #[test]
+    fn test_apply_custom_headers() {
+        let http_config = HttpConfig {
+            http_headers: [(
+                "X-Scope-OrgID".into(),
+                HttpHeader {
+                    values: vec!["tenant-1".into()],
+                    secrets: vec!["tenant-2".into()],
+                    files: vec![],
+                },
+            )]
+            .into(),
+            ..Default::default()
+        };
+        let values: Vec<_> = build_request(&http_config)
+            .headers()
+            .get_all("X-Scope-OrgID")
+            .iter()
+            .map(|v| v.to_str().unwrap().to_owned())
+            .collect();
+        assert_eq!(values, vec!["tenant-1", "tenant-2"]);
+    }
+}
@@ -1,10 +1,17 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
-use serde::Deserialize;
+use serde::{de::IgnoredAny, Deserialize};
 
-use crate::baseurl::BaseUrl;
+use crate::{baseurl::BaseUrl, version_scheme::VersionScheme};
 
-use super::{version_extractor::VersionExtractor, VersionInfo};
+use super::{
+    deserialize_duration_secs, error::Error, http_config::HttpConfig,
+    version_extractor::VersionExtractor, version_template::VersionTemplate, ReleaseMetadata,
+    VersionInfo,
+};
 
 fn default_prometheus_url() -> BaseUrl {
     BaseUrl::parse("http://localhost:9090/api/").unwrap()
@@ -14,6 +21,16 @@ fn default_version_label() -> String {
     "version".into()
 }
 
+/// Where to take the version from for `vector` and `matrix` results. `scalar`
+/// and `string` results do not have labels and always use the sample value.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionSource {
+    #[default]
+    Label,
+    Value,
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct Provider {
     pub query: String,
@@ -21,36 +38,130 @@ pub struct Provider {
     #[serde(default = "default_version_label")]
     pub label: String,
 
+    #[serde(default)]
+    pub version_source: VersionSource,
+
+    /// Composes the version from several labels. Takes precedence over
+    /// `label` and `version_source`.
+    pub version_template: Option<VersionTemplate>,
+
+    /// If set, only these labels are attached to the releases.
+    pub keep_labels: Option<Vec<String>>,
+
+    #[serde(default)]
+    pub drop_labels: Vec<String>,
+
     #[serde(flatten)]
     pub version_extractor: VersionExtractor,
 
+    #[serde(default)]
+    pub version_scheme: VersionScheme,
+
     #[serde(default = "default_prometheus_url")]
     pub api_url: BaseUrl,
+
+    /// Evaluation timestamp passed verbatim to the Prometheus API
+    /// (RFC 3339 or Unix timestamp). Takes precedence over `offset`.
+    pub time: Option<String>,
+
+    #[serde(
+        rename = "offset_seconds",
+        deserialize_with = "deserialize_duration_secs",
+        default = "Duration::default"
+    )]
+    pub offset: Duration,
+
+    #[serde(default)]
+    pub fail_on_warnings: bool,
+
+    #[serde(default)]
+    pub http_config: HttpConfig,
+
+    /// Client used instead of the shared one if the `http_config` requires
+    /// dedicated connection settings. Set up by [`Provider::configure_http_client`].
+    #[serde(skip)]
+    pub http_client: Option<reqwest::Client>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
-struct QueryResponse {
-    data: QueryResponseData,
+#[serde(tag = "status", rename_all = "snake_case")]
+enum QueryResponse {
+    Success {
+        data: QueryResponseData,
+        #[serde(default)]
+        warnings: Vec<String>,
+    },
+    Error {
+        #[serde(rename = "errorType")]
+        error_type: String,
+        error: String,
+    },
 }
 
 #[derive(Clone, Debug, Deserialize)]
-struct QueryResponseData {
-    result: Vec<QueryResultItem>,
+#[serde(tag = "resultType", content = "result", rename_all = "snake_case")]
+enum QueryResponseData {
+    Vector(Vec<VectorItem>),
+    Matrix(Vec<MatrixItem>),
+    Scalar(Sample),
+    String(Sample),
 }
 
+/// A sample consisting of the timestamp (ignored) and the value.
 #[derive(Clone, Debug, Deserialize)]
-struct QueryResultItem {
+struct Sample(IgnoredAny, String);
+
+#[derive(Clone, Debug, Deserialize)]
+struct VectorItem {
+    metric: Metric,
+    value: Sample,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct MatrixItem {
     metric: Metric,
+    values: Vec<Sample>,
 }
 
+/// The labels of a series. The metric name is not attached to releases and
+/// might be missing, e.g. for results of aggregations.
 #[derive(Clone, Debug, Deserialize)]
 struct Metric {
-    __name__: String,
+    __name__: Option<String>,
     #[serde(flatten)]
     labels: HashMap<String, String>,
 }
 
 impl Provider {
+    pub fn configure_http_client<F>(&mut self, client_builder: F) -> super::error::Result<()>
+    where
+        F: Fn() -> reqwest::ClientBuilder,
+    {
+        self.http_config.validate()?;
+        if self.http_config.requires_dedicated_client() {
+            self.http_client = Some(
+                self.http_config
+                    .configure_client(client_builder())?
+                    .build()?,
+            );
+        }
+        Ok(())
+    }
+
+    fn evaluation_time(&self) -> Option<String> {
+        if let Some(time) = &self.time {
+            return Some(time.clone());
+        }
+        if self.offset.is_zero() {
+            return None;
+        }
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .saturating_sub(self.offset);
+        Some(format!("{:.3}", timestamp.as_secs_f64()))
+    }
+
     pub async fn fetch(
         &self,
         http_client: &reqwest::Client,
@@ -58,56 +169,124 @@ impl Provider {
         let mut url = self.api_url.clone();
         url.extend(["v1", "query"]);
         url.query_pairs_mut().append_pair("query", &self.query);
+        if let Some(time) = self.evaluation_time() {
+            url.query_pairs_mut().append_pair("time", &time);
+        }
 
-        let api_response: QueryResponse = http_client
+        let http_client = self.http_client.as_ref().unwrap_or(http_client);
+        let request = http_client
             .get(url.into_url())
-            .header("Accept", "application/json")
-            .send()
-            .await?
-            .error_for_status()?
-            .json()
-            .await?;
-        Ok(api_response
-            .data
-            .result
-            .into_iter()
-            .map(|mut result| {
-                let version = result
-                    .metric
-                    .labels
-                    .remove(&self.label)
-                    .and_then(|v| self.version_extractor.extract(&v));
-                VersionInfo {
-                    version,
-                    labels: result.metric.labels,
+            .header("Accept", "application/json");
+        let response = self.http_config.apply(request)?.send().await?;
+        let status = response.status();
+        let api_response: QueryResponse = match response.json().await {
+            Ok(api_response) => api_response,
+            Err(_) if !status.is_success() => return Err(Error::HttpStatus { status }),
+            Err(err) => return Err(err.into()),
+        };
+
+        let data = match api_response {
+            QueryResponse::Error { error_type, error } => {
+                return Err(Error::PrometheusQuery { error_type, error })
+            }
+            QueryResponse::Success { data, warnings } => {
+                if !warnings.is_empty() {
+                    if self.fail_on_warnings {
+                        return Err(Error::PrometheusWarnings { warnings });
+                    }
+                    for warning in warnings {
+                        tide::log::warn!("Prometheus query {} warning: {}", self.query, warning);
+                    }
+                }
+                data
+            }
+        };
+
+        Ok(match data {
+            QueryResponseData::Vector(result) => result
+                .into_iter()
+                .map(|item| self.version_info(item.metric, Some(item.value)))
+                .collect(),
+            QueryResponseData::Matrix(result) => result
+                .into_iter()
+                .map(|mut item| self.version_info(item.metric, item.values.pop()))
+                .collect(),
+            QueryResponseData::Scalar(sample) | QueryResponseData::String(sample) => {
+                vec![VersionInfo {
+                    version: self.version_extractor.extract(&sample.1),
+                    labels: HashMap::new(),
+                    metadata: ReleaseMetadata::default(),
+                }]
+            }
+        })
+    }
+
+    fn version_info(&self, mut metric: Metric, sample: Option<Sample>) -> VersionInfo {
+        let version = match (&self.version_template, self.version_source) {
+            (Some(template), _) => {
+                let version = template.render(&metric.labels);
+                for label in template.labels() {
+                    metric.labels.remove(label);
                 }
-            })
-            .collect())
+                version
+            }
+            (None, VersionSource::Label) => metric.labels.remove(&self.label),
+            (None, VersionSource::Value) => sample.map(|sample| sample.1),
+        };
+        if let Some(keep_labels) = &self.keep_labels {
+            metric.labels.retain(|label, _| keep_labels.contains(label));
+        }
+        for label in &self.drop_labels {
+            metric.labels.remove(label);
+        }
+        VersionInfo {
+            version: version.and_then(|v| self.version_extractor.extract(&v)),
+            labels: metric.labels,
+            metadata: ReleaseMetadata::default(),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::{collections::HashMap, time::Duration};
 
     use crate::{
         providers::{
-            prometheus::{default_version_label, Provider},
+            error::Error,
+            http_config::{Authorization, HttpConfig, HttpHeader},
+            prometheus::{default_version_label, Metric, Provider, VersionSource},
             version_extractor::VersionExtractor,
-            VersionInfo,
+            version_template::VersionTemplate,
+            ReleaseMetadata, VersionInfo,
         },
         test_config::prometheus_api_url,
+        version_scheme::VersionScheme,
     };
 
-    #[tokio::test]
-    async fn test_fetch_prometheus_versions() {
-        let client = reqwest::Client::new();
-        let provider = Provider {
-            query: "dmarc_metrics_exporter_build_info".into(),
+    fn provider(query: &str) -> Provider {
+        Provider {
+            query: query.into(),
             label: default_version_label(),
+            version_source: VersionSource::Label,
+            version_template: None,
+            keep_labels: None,
+            drop_labels: vec![],
             version_extractor: VersionExtractor::default(),
+            version_scheme: VersionScheme::default(),
             api_url: prometheus_api_url(),
-        };
+            time: None,
+            offset: Duration::default(),
+            fail_on_warnings: false,
+            http_config: HttpConfig::default(),
+            http_client: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_prometheus_versions() {
+        let client = reqwest::Client::new();
+        let provider = provider("dmarc_metrics_exporter_build_info");
         let releases = provider.fetch(&client).await.unwrap();
 
         let mut expected_labels = HashMap::new();
@@ -118,8 +297,200 @@ mod tests {
             releases,
             vec![VersionInfo {
                 version: Some("0.8.0".into()),
-                labels: expected_labels
+                labels: expected_labels,
+                metadata: ReleaseMetadata::default(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_prometheus_versions_with_http_config() {
+        let client = reqwest::Client::new();
+        let mut provider = Provider {
+            http_config: HttpConfig {
+                authorization: Some(Authorization {
+                    auth_type: "Bearer".into(),
+                    credentials: Some("secret-token".into()),
+                    credentials_file: None,
+                }),
+                http_headers: [(
+                    "X-Scope-OrgID".into(),
+                    HttpHeader {
+                        values: vec!["tenant-1".into()],
+                        ..Default::default()
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+            ..provider("authenticated_dmarc_metrics_exporter_build_info")
+        };
+        provider
+            .configure_http_client(reqwest::Client::builder)
+            .unwrap();
+        assert!(provider.http_client.is_none());
+
+        let releases = provider.fetch(&client).await.unwrap();
+        assert_eq!(releases.len(), 1);
+        assert_eq!(releases[0].version, Some("0.8.0".into()));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_prometheus_version_from_value() {
+        let client = reqwest::Client::new();
+        let provider = Provider {
+            version_source: VersionSource::Value,
+            version_extractor: serde_yaml::from_str(
+                r#"{version_regex: '^(\d+)0(\d)0(\d)$', version_fmt: '$1.$2.$3'}"#,
+            )
+            .unwrap(),
+            time: Some("2024-01-01T00:00:00Z".into()),
+            ..provider("numeric_version")
+        };
+        let releases = provider.fetch(&client).await.unwrap();
+        assert_eq!(
+            releases,
+            vec![VersionInfo {
+                version: Some("1.2.3".into()),
+                labels: HashMap::from([("job".into(), "numeric".into())]),
+                metadata: ReleaseMetadata::default(),
             }]
         );
     }
+
+    #[tokio::test]
+    async fn test_fetch_prometheus_matrix_uses_last_sample() {
+        let client = reqwest::Client::new();
+        let provider = Provider {
+            version_source: VersionSource::Value,
+            ..provider("numeric_version[5m]")
+        };
+        let releases = provider.fetch(&client).await.unwrap();
+        assert_eq!(
+            releases,
+            vec![VersionInfo {
+                version: Some("10203".into()),
+                labels: HashMap::from([("job".into(), "numeric".into())]),
+                metadata: ReleaseMetadata::default(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_prometheus_scalar_and_string() {
+        let client = reqwest::Client::new();
+        for query in ["scalar(numeric_version)", "\"v1.2.3\""] {
+            let releases = provider(query).fetch(&client).await.unwrap();
+            assert_eq!(releases.len(), 1, "query: {query}");
+            assert!(releases[0].labels.is_empty(), "query: {query}");
+        }
+        let releases = provider("\"v1.2.3\"").fetch(&client).await.unwrap();
+        assert_eq!(releases[0].version, Some("1.2.3".into()));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_prometheus_error_response() {
+        let client = reqwest::Client::new();
+        let error = provider("invalid{").fetch(&client).await.unwrap_err();
+        assert!(
+            matches!(&error, Error::PrometheusQuery { error_type, error: _ } if error_type == "bad_data"),
+            "unexpected error: {error}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_prometheus_warnings() {
+        let client = reqwest::Client::new();
+        let releases = provider("partial_response").fetch(&client).await.unwrap();
+        assert_eq!(releases.len(), 1);
+
+        let provider = Provider {
+            fail_on_warnings: true,
+            ..provider("partial_response")
+        };
+        let error = provider.fetch(&client).await.unwrap_err();
+        assert!(
+            matches!(&error, Error::PrometheusWarnings { warnings } if warnings.len() == 1),
+            "unexpected error: {error}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_prometheus_aggregated_versions() {
+        let client = reqwest::Client::new();
+        let cases = [
+            (
+                "sum by (deployment, version) (app_build_info)",
+                vec![
+                    VersionInfo {
+                        version: Some("1.2.3".into()),
+                        labels: HashMap::from([("deployment".into(), "api".into())]),
+                        metadata: ReleaseMetadata::default(),
+                    },
+                    VersionInfo {
+                        version: Some("1.2.2".into()),
+                        labels: HashMap::from([("deployment".into(), "worker".into())]),
+                        metadata: ReleaseMetadata::default(),
+                    },
+                ],
+            ),
+            (
+                "topk(1, app_build_info)",
+                vec![VersionInfo {
+                    version: Some("1.2.3".into()),
+                    labels: HashMap::from([
+                        ("deployment".into(), "api".into()),
+                        ("pod".into(), "api-5d8f7".into()),
+                    ]),
+                    metadata: ReleaseMetadata::default(),
+                }],
+            ),
+            (
+                r#"label_replace(app_build_info, "version", "$1", "image_tag", "v(.*)")"#,
+                vec![VersionInfo {
+                    version: Some("1.2.3".into()),
+                    labels: HashMap::from([
+                        ("deployment".into(), "api".into()),
+                        ("image_tag".into(), "v1.2.3".into()),
+                        ("pod".into(), "api-5d8f7".into()),
+                    ]),
+                    metadata: ReleaseMetadata::default(),
+                }],
+            ),
+        ];
+        for (query, expected) in cases {
+            let releases = provider(query).fetch(&client).await.unwrap();
+            assert_eq!(releases, expected, "query: {query}");
+        }
+    }
+
+    #[test]
+    fn test_version_template_and_label_filters() {
+        let metric = Metric {
+            __name__: Some("build_info".into()),
+            labels: HashMap::from([
+                ("major".into(), "1".into()),
+                ("minor".into(), "2".into()),
+                ("patch".into(), "3".into()),
+                ("deployment".into(), "api".into()),
+                ("namespace".into(), "prod".into()),
+                ("pod".into(), "api-5d8f7".into()),
+            ]),
+        };
+
+        let provider = Provider {
+            version_template: VersionTemplate::parse("{major}.{minor}.{patch}"),
+            keep_labels: Some(vec!["deployment".into(), "namespace".into()]),
+            drop_labels: vec!["namespace".into()],
+            ..provider("build_info")
+        };
+        assert_eq!(
+            provider.version_info(metric, None),
+            VersionInfo {
+                version: Some("1.2.3".into()),
+                labels: HashMap::from([("deployment".into(), "api".into())]),
+                metadata: ReleaseMetadata::default(),
+            }
+        );
+    }
 }
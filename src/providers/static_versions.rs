@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::{normalization::Normalization, version_scheme::VersionScheme};
+
+use super::{ReleaseMetadata, VersionInfo};
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct StaticVersion {
+    pub version: String,
+
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+}
+
+impl From<StaticVersion> for VersionInfo {
+    fn from(version: StaticVersion) -> Self {
+        Self {
+            version: Some(version.version),
+            labels: version.labels,
+            metadata: ReleaseMetadata::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Provider {
+    pub versions: Vec<StaticVersion>,
+
+    #[serde(default)]
+    pub version_scheme: VersionScheme,
+
+    #[serde(default)]
+    pub normalize: Normalization,
+}
+
+impl Provider {
+    pub fn fetch(&self) -> Vec<VersionInfo> {
+        self.versions
+            .iter()
+            .cloned()
+            .map(|version| VersionInfo {
+                version: Some(self.normalize.apply(&version.version).into_owned()),
+                ..version.into()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::providers::{
+        static_versions::{Provider, StaticVersion},
+        ReleaseMetadata, VersionInfo,
+    };
+
+    #[test]
+    fn test_fetch_static_versions() {
+        let provider: Provider = serde_yaml::from_str(
+            r#"
+            versions:
+              - version: 1.2.3
+                labels:
+                  stream: stable
+              - version: 2.0.0-rc.1
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            provider.versions,
+            vec![
+                StaticVersion {
+                    version: "1.2.3".into(),
+                    labels: HashMap::from([("stream".into(), "stable".into())]),
+                },
+                StaticVersion {
+                    version: "2.0.0-rc.1".into(),
+                    labels: HashMap::new(),
+                },
+            ]
+        );
+
+        assert_eq!(
+            provider.fetch(),
+            vec![
+                VersionInfo {
+                    version: Some("1.2.3".into()),
+                    labels: HashMap::from([("stream".into(), "stable".into())]),
+                    metadata: ReleaseMetadata::default(),
+                },
+                VersionInfo {
+                    version: Some("2.0.0-rc.1".into()),
+                    labels: HashMap::new(),
+                    metadata: ReleaseMetadata::default(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fetch_normalized_static_versions() {
+        let provider: Provider = serde_yaml::from_str(
+            r#"
+            versions:
+              - version: V1.2
+            normalize: [lowercase, pad_release]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(provider.fetch()[0].version.as_deref(), Some("v1.2.0"));
+    }
+}
@@ -1,6 +1,9 @@
+use prometheus_client::metrics::counter::Counter;
 use regex::Regex;
 use serde::Deserialize;
 
+use crate::normalization::Normalization;
+
 fn default_version_regex() -> Regex {
     Regex::new(r"^v?(.*)$").unwrap()
 }
@@ -9,37 +12,156 @@ fn default_version_fmt() -> String {
     "${1}".into()
 }
 
+fn default_reject_unmatched() -> bool {
+    true
+}
+
+/// A regular expression to match a version with a format to construct the
+/// version from the capture groups.
 #[derive(Clone, Debug, Deserialize)]
-pub struct VersionExtractor {
-    #[serde(default = "default_version_regex", with = "serde_regex")]
-    version_regex: Regex,
+pub struct ExtractionRule {
+    #[serde(with = "serde_regex")]
+    regex: Regex,
 
     #[serde(default = "default_version_fmt")]
-    version_fmt: String,
+    fmt: String,
 }
 
-impl Default for VersionExtractor {
+impl Default for ExtractionRule {
     fn default() -> Self {
         Self {
-            version_regex: default_version_regex(),
-            version_fmt: default_version_fmt(),
+            regex: default_version_regex(),
+            fmt: default_version_fmt(),
         }
     }
 }
 
-impl VersionExtractor {
-    pub fn extract(&self, version: &str) -> Option<String> {
-        self.version_regex.find(version).map(|version_match| {
-            self.version_regex
+impl ExtractionRule {
+    fn extract(&self, version: &str) -> Option<String> {
+        self.regex.find(version).map(|version_match| {
+            self.regex
                 .replace(
                     &version[version_match.start()..version_match.end()],
-                    &self.version_fmt,
+                    &self.fmt,
                 )
                 .into()
         })
     }
 }
 
+#[derive(Clone, Debug, Deserialize)]
+struct VersionExtractorConfig {
+    #[serde(default, with = "serde_regex")]
+    version_regex: Option<Regex>,
+
+    version_fmt: Option<String>,
+
+    version_rules: Option<Vec<ExtractionRule>>,
+
+    #[serde(default = "default_reject_unmatched")]
+    reject_unmatched: bool,
+
+    #[serde(default)]
+    normalize: Normalization,
+}
+
+/// Extracts versions with a list of rules tried in order. Either configured
+/// with a single `version_regex` and `version_fmt` or a list of
+/// `version_rules`. Extracted versions are normalized afterwards.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(try_from = "VersionExtractorConfig")]
+pub struct VersionExtractor {
+    rules: Vec<ExtractionRule>,
+    reject_unmatched: bool,
+    normalize: Normalization,
+    rejected: Counter,
+}
+
+impl TryFrom<VersionExtractorConfig> for VersionExtractor {
+    type Error = String;
+
+    fn try_from(config: VersionExtractorConfig) -> Result<Self, Self::Error> {
+        let rules = match (
+            config.version_rules,
+            config.version_regex,
+            config.version_fmt,
+        ) {
+            (Some(_), Some(_), _) | (Some(_), _, Some(_)) => {
+                return Err(
+                    "version_rules cannot be combined with version_regex or version_fmt".into(),
+                )
+            }
+            (Some(rules), None, None) if rules.is_empty() => {
+                return Err("version_rules must not be empty".into())
+            }
+            (Some(rules), None, None) => rules,
+            (None, regex, fmt) => vec![ExtractionRule {
+                regex: regex.unwrap_or_else(default_version_regex),
+                fmt: fmt.unwrap_or_else(default_version_fmt),
+            }],
+        };
+        Ok(Self {
+            rules,
+            reject_unmatched: config.reject_unmatched,
+            normalize: config.normalize,
+            rejected: Counter::default(),
+        })
+    }
+}
+
+impl Default for VersionExtractor {
+    fn default() -> Self {
+        Self {
+            rules: vec![ExtractionRule::default()],
+            reject_unmatched: default_reject_unmatched(),
+            normalize: Normalization::default(),
+            rejected: Counter::default(),
+        }
+    }
+}
+
+impl VersionExtractor {
+    /// Uses the given counter to count values rejected for not matching any
+    /// rule.
+    pub fn set_rejected_counter(&mut self, counter: Counter) {
+        self.rejected = counter;
+    }
+
+    pub fn extract(&self, version: &str) -> Option<String> {
+        self.extract_unnormalized(version)
+            .map(|version| self.normalize.apply(&version).into_owned())
+    }
+
+    fn extract_unnormalized(&self, version: &str) -> Option<String> {
+        let extracted = self
+            .rules
+            .iter()
+            .enumerate()
+            .find_map(|(index, rule)| rule.extract(version).map(|v| (index, v)));
+        match extracted {
+            Some((index, extracted)) => {
+                tide::log::debug!(
+                    "Extracted version {} from {} with rule {} ({})",
+                    extracted,
+                    version,
+                    index,
+                    self.rules[index].regex
+                );
+                Some(extracted)
+            }
+            None if self.reject_unmatched => {
+                tide::log::debug!("Rejected version {} matching no rule", version);
+                self.rejected.inc();
+                None
+            }
+            None => {
+                tide::log::debug!("Keeping version {} matching no rule", version);
+                Some(version.into())
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::VersionExtractor;
@@ -55,4 +177,48 @@ mod tests {
             String::from("1.2.3")
         );
     }
+
+    #[test]
+    fn test_extract_version_with_rules() {
+        let mut extractor: VersionExtractor = serde_yaml::from_str(
+            r#"
+            version_rules:
+              - regex: '^release-(\d+)\.(\d+)$'
+                fmt: '${1}.${2}.0'
+              - regex: '^v(\d+\.\d+\.\d+)$'
+            "#,
+        )
+        .unwrap();
+        assert_eq!(extractor.extract("release-1.2"), Some("1.2.0".into()));
+        assert_eq!(extractor.extract("v1.3.0"), Some("1.3.0".into()));
+        assert_eq!(extractor.extract("nightly"), None);
+        assert_eq!(extractor.extract("latest"), None);
+        assert_eq!(extractor.rejected.get(), 2);
+
+        extractor.reject_unmatched = false;
+        assert_eq!(extractor.extract("nightly"), Some("nightly".into()));
+        assert_eq!(extractor.rejected.get(), 2);
+    }
+
+    #[test]
+    fn test_extract_normalized_version() {
+        let extractor: VersionExtractor =
+            serde_yaml::from_str("normalize: [strip_build_metadata, pad_release]").unwrap();
+        assert_eq!(extractor.extract("v1.2+build.5"), Some("1.2.0".into()));
+    }
+
+    #[test]
+    fn test_deserialize_conflicting_version_rules() {
+        let result = serde_yaml::from_str::<VersionExtractor>(
+            r#"
+            version_regex: '^v(.*)$'
+            version_rules:
+              - regex: '^(.*)$'
+            "#,
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "version_rules cannot be combined with version_regex or version_fmt"
+        );
+    }
 }
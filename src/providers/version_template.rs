@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+
+use serde::{de::Unexpected, de::Visitor, Deserialize, Deserializer};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum TemplatePart {
+    Literal(String),
+    Label(String),
+}
+
+/// Composes a version from several labels, e.g. `{major}.{minor}.{patch}`.
+/// Use `{{` and `}}` for literal braces.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VersionTemplate {
+    parts: Vec<TemplatePart>,
+}
+
+impl VersionTemplate {
+    pub fn parse(template: &str) -> Option<Self> {
+        let mut parts = vec![];
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut label = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some('{') | None => return None,
+                            Some(c) => label.push(c),
+                        }
+                    }
+                    if label.is_empty() {
+                        return None;
+                    }
+                    if !literal.is_empty() {
+                        parts.push(TemplatePart::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(TemplatePart::Label(label));
+                }
+                '}' => return None,
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            parts.push(TemplatePart::Literal(literal));
+        }
+        Some(Self { parts })
+    }
+
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.parts.iter().filter_map(|part| match part {
+            TemplatePart::Label(label) => Some(label.as_str()),
+            TemplatePart::Literal(_) => None,
+        })
+    }
+
+    /// Renders the template. Returns `None` if any referenced label is missing.
+    pub fn render(&self, labels: &HashMap<String, String>) -> Option<String> {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                TemplatePart::Literal(literal) => Some(literal.as_str()),
+                TemplatePart::Label(label) => labels.get(label).map(String::as_str),
+            })
+            .collect()
+    }
+}
+
+impl<'de> Deserialize<'de> for VersionTemplate {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(VersionTemplateVisitor)
+    }
+}
+
+struct VersionTemplateVisitor;
+
+impl<'de> Visitor<'de> for VersionTemplateVisitor {
+    type Value = VersionTemplate;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a version template referencing labels like {major}.{minor}")
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        VersionTemplate::parse(s)
+            .ok_or_else(|| serde::de::Error::invalid_value(Unexpected::Str(s), &self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_test::{assert_de_tokens_error, Token};
+
+    use super::VersionTemplate;
+
+    #[test]
+    fn test_render_version_template() {
+        let template = VersionTemplate::parse("{major}.{minor}.{patch}{{x}}").unwrap();
+        assert_eq!(
+            template.labels().collect::<Vec<_>>(),
+            vec!["major", "minor", "patch"]
+        );
+
+        let mut labels = HashMap::from([
+            ("major".into(), "1".into()),
+            ("minor".into(), "2".into()),
+            ("patch".into(), "3".into()),
+        ]);
+        assert_eq!(template.render(&labels), Some("1.2.3{x}".into()));
+
+        labels.remove("patch");
+        assert_eq!(template.render(&labels), None);
+    }
+
+    #[test]
+    fn test_deserialize_invalid_version_template() {
+        for template in ["{major", "major}", "{}", "{{major}"] {
+            assert_de_tokens_error::<VersionTemplate>(
+                &[Token::Str(template)],
+                &format!("invalid value: string \"{template}\", expected a version template referencing labels like {{major}}.{{minor}}"),
+            );
+        }
+    }
+}
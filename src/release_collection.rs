@@ -1,4 +1,7 @@
-use std::{collections::HashMap, time::Duration};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
 
 use futures::StreamExt;
 use reqwest::Client;
@@ -14,26 +17,33 @@ pub struct Release {
 pub struct ReleaseCollection {
     pub releases: HashMap<String, Release>,
     pub errors: HashMap<String, Box<dyn std::error::Error + Send + Sync>>,
+    pub fetch_durations: HashMap<String, Duration>,
 }
 
 impl ReleaseCollection {
     pub async fn collect_from(providers: Vec<Provider>, http_client: &Client) -> Self {
         let results: HashMap<_, _> = stream::iter(providers)
             .map(|p| async move {
+                let start = Instant::now();
+                let versions = p.versions(http_client).await;
                 (
                     p.name().to_string(),
-                    (p.versions(http_client).await, *p.cache_duration()),
+                    (versions, *p.cache_duration(), start.elapsed()),
                 )
             })
             .buffer_unordered(10)
             .collect()
             .await;
+        let fetch_durations = results
+            .iter()
+            .map(|(k, (_, _, fetch_duration))| (k.clone(), *fetch_duration))
+            .collect();
         let (releases, errors): (HashMap<_, _>, HashMap<_, _>) =
-            results.into_iter().partition(|(_, (r, _))| r.is_ok());
+            results.into_iter().partition(|(_, (r, _, _))| r.is_ok());
         Self {
             releases: releases
                 .into_iter()
-                .map(|(k, (v, cache_duration))| {
+                .map(|(k, (v, cache_duration, _))| {
                     (
                         k,
                         Release {
@@ -45,8 +55,9 @@ impl ReleaseCollection {
                 .collect(),
             errors: errors
                 .into_iter()
-                .map(|(k, (v, _))| (k, v.unwrap_err()))
+                .map(|(k, (v, _, _))| (k, v.unwrap_err()))
                 .collect(),
+            fetch_durations,
         }
     }
 }
@@ -0,0 +1,204 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+    time::SystemTime,
+};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use tide::{Request, Response};
+
+use crate::{checks::upgrade_pending::LabeledStatus, State};
+
+const STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em; }
+table { border-collapse: collapse; margin-bottom: 2em; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; }
+th { background: #eee; }
+.up-to-date { background: #dfd; }
+.upgrade-available { background: #fdd; }
+.ahead-of-latest, .incomparable, .unknown { background: #ffd; }
+.error { color: #a00; }
+";
+
+/// Escapes text for the use in HTML content and attribute values.
+struct Escaped<'a>(&'a str);
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '&' => f.write_str("&amp;")?,
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                '"' => f.write_str("&quot;")?,
+                '\'' => f.write_str("&#39;")?,
+                c => f.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn format_labels(labels: &HashMap<String, String>) -> String {
+    let mut labels: Vec<_> = labels
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect();
+    labels.sort();
+    labels.join(", ")
+}
+
+fn format_time(time: Option<SystemTime>) -> String {
+    time.map(|time| DateTime::<Utc>::from(time).to_rfc3339_opts(SecondsFormat::Secs, true))
+        .unwrap_or_default()
+}
+
+struct CheckRow<'a> {
+    check: &'a str,
+    result: LabeledStatus<'a>,
+}
+
+struct ProviderRow<'a> {
+    name: &'a str,
+    versions: usize,
+    last_success: Option<SystemTime>,
+    error: Option<&'a str>,
+}
+
+fn render(checks: &[CheckRow], providers: &[ProviderRow]) -> Result<String, fmt::Error> {
+    let mut html = String::new();
+    writeln!(html, "<!DOCTYPE html>")?;
+    writeln!(html, "<html>")?;
+    writeln!(html, "<head>")?;
+    writeln!(html, "<meta charset=\"utf-8\">")?;
+    writeln!(html, "<title>release-exporter</title>")?;
+    writeln!(html, "<style>\n{STYLE}</style>")?;
+    writeln!(html, "</head>")?;
+    writeln!(html, "<body>")?;
+    writeln!(html, "<h1>release-exporter</h1>")?;
+    writeln!(
+        html,
+        "<p><a href=\"metrics\">Metrics</a> | <a href=\"api/v1/releases\">Releases (JSON)</a> | <a href=\"api/v1/checks\">Checks (JSON)</a></p>"
+    )?;
+
+    writeln!(html, "<h2>Checks</h2>")?;
+    writeln!(html, "<table>")?;
+    writeln!(
+        html,
+        "<tr><th>Check</th><th>Labels</th><th>Current version</th><th>Latest version</th><th>Status</th><th>Severity</th></tr>"
+    )?;
+    for row in checks {
+        let status = row.result.status.as_str();
+        writeln!(
+            html,
+            "<tr class=\"{status}\"><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{status}</td><td>{}</td></tr>",
+            Escaped(row.check),
+            Escaped(&format_labels(row.result.labels)),
+            Escaped(row.result.current_version.unwrap_or_default()),
+            Escaped(row.result.latest_version.unwrap_or_default()),
+            row.result.severity.map_or("", |severity| severity.as_str()),
+        )?;
+    }
+    writeln!(html, "</table>")?;
+
+    writeln!(html, "<h2>Providers</h2>")?;
+    writeln!(html, "<table>")?;
+    writeln!(
+        html,
+        "<tr><th>Provider</th><th>Versions</th><th>Last successful fetch</th><th>Error</th></tr>"
+    )?;
+    for row in providers {
+        writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td class=\"error\">{}</td></tr>",
+            Escaped(row.name),
+            row.versions,
+            format_time(row.last_success),
+            Escaped(row.error.unwrap_or_default()),
+        )?;
+    }
+    writeln!(html, "</table>")?;
+    writeln!(html, "</body>")?;
+    writeln!(html, "</html>")?;
+    Ok(html)
+}
+
+/// Renders the results of all checks and the state of all providers as HTML.
+pub async fn index(req: Request<State>) -> tide::Result {
+    let state = req.state();
+    let releases = state.fetch().await;
+    let errors = state.errors.lock().unwrap().clone();
+    let last_success = state.last_success.lock().unwrap().clone();
+
+    let checks: Vec<_> = state
+        .config
+        .upgrade_pending_checks
+        .iter()
+        .flat_map(|check| {
+            check.check(&releases).into_iter().map(|result| CheckRow {
+                check: &check.name,
+                result,
+            })
+        })
+        .collect();
+    let providers: Vec<_> = state
+        .config
+        .providers
+        .iter()
+        .map(|provider| ProviderRow {
+            name: provider.name(),
+            versions: releases.get(provider.name()).map_or(0, Vec::len),
+            last_success: last_success.get(provider.name()).copied(),
+            error: errors.get(provider.name()).map(String::as_str),
+        })
+        .collect();
+
+    Ok(Response::builder(200)
+        .content_type(tide::http::mime::HTML)
+        .body(render(&checks, &providers)?)
+        .build())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    use crate::{
+        checks::upgrade_pending::{CheckStatus, LabeledStatus},
+        test_config::labeled_status,
+        version_scheme::Severity,
+    };
+
+    use super::{render, CheckRow, ProviderRow};
+
+    #[test]
+    fn test_render() {
+        let labels = HashMap::from([("team".into(), "<a&b>".into())]);
+        let checks = [CheckRow {
+            check: "app",
+            result: LabeledStatus {
+                current_version: Some("1.0.0"),
+                severity: Some(Severity::Minor),
+                latest_version: Some("1.1.0"),
+                ..labeled_status(&labels, CheckStatus::UpgradeAvailable)
+            },
+        }];
+        let providers = [ProviderRow {
+            name: "latest_app_release",
+            versions: 1,
+            last_success: Some(UNIX_EPOCH + Duration::from_secs(1700000000)),
+            error: Some("unexpected HTTP status: 404 Not Found"),
+        }];
+
+        let html = render(&checks, &providers).unwrap();
+        assert!(html.contains(
+            "<tr class=\"upgrade-available\"><td>app</td><td>team=&lt;a&amp;b&gt;</td><td>1.0.0</td><td>1.1.0</td><td>upgrade-available</td><td>minor</td></tr>"
+        ));
+        assert!(html.contains(
+            "<tr><td>latest_app_release</td><td>1</td><td>2023-11-14T22:13:20Z</td><td class=\"error\">unexpected HTTP status: 404 Not Found</td></tr>"
+        ));
+    }
+}
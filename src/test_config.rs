@@ -1,6 +1,14 @@
-use std::env::VarError;
+use std::{
+    collections::HashMap,
+    env::VarError,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
-use crate::baseurl::BaseUrl;
+use crate::{
+    baseurl::BaseUrl,
+    checks::upgrade_pending::{CheckStatus, LabeledStatus},
+};
 
 pub fn github_api_url() -> BaseUrl {
     static DEFAULT_TEST_API_URL: &str = "http://localhost:8080/github";
@@ -26,3 +34,52 @@ pub fn prometheus_api_url() -> BaseUrl {
         .unwrap_or_else(|_: VarError| DEFAULT_TEST_API_URL.into());
     BaseUrl::parse(&url).unwrap()
 }
+
+/// A file in the temporary directory with a unique name that is removed when
+/// dropped.
+pub struct TempFile {
+    path: PathBuf,
+}
+
+impl TempFile {
+    pub fn new(content: &str) -> Self {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let path = std::env::temp_dir().join(format!(
+            "release-exporter-test-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let file = TempFile { path };
+        file.write(content);
+        file
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn write(&self, content: &str) {
+        std::fs::write(&self.path, content).unwrap();
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// A check result without any of the optional values set.
+pub fn labeled_status(labels: &HashMap<String, String>, status: CheckStatus) -> LabeledStatus<'_> {
+    LabeledStatus {
+        labels,
+        current_version: None,
+        status,
+        severity: None,
+        latest_version: None,
+        releases_behind: None,
+        latest_published_at: None,
+        upgrade_pending_since: None,
+        unconstrained_latest_version: None,
+    }
+}
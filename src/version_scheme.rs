@@ -0,0 +1,219 @@
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+
+mod debian;
+mod loose;
+mod maven;
+mod pep440;
+
+/// The most significant component in which two versions differ.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Major,
+    Minor,
+    Patch,
+    Prerelease,
+    Build,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Major => "major",
+            Severity::Minor => "minor",
+            Severity::Patch => "patch",
+            Severity::Prerelease => "prerelease",
+            Severity::Build => "build",
+        }
+    }
+}
+
+/// Classifies the difference of numeric release components where the first
+/// component is the major, the second the minor, and any further component a
+/// patch version. Missing components are treated as zero.
+fn release_severity(a: &[u64], b: &[u64]) -> Option<Severity> {
+    let index =
+        (0..a.len().max(b.len())).find(|&i| a.get(i).unwrap_or(&0) != b.get(i).unwrap_or(&0))?;
+    Some(match index {
+        0 => Severity::Major,
+        1 => Severity::Minor,
+        _ => Severity::Patch,
+    })
+}
+
+/// Compares numeric release components where missing components are treated
+/// as zero.
+pub fn compare_release(a: &[u64], b: &[u64]) -> Ordering {
+    (0..a.len().max(b.len()))
+        .map(|i| a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0)))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// The leading numeric release components of a version, e.g. `[1, 4, 2]` for
+/// `v1.4.2-rc1`.
+pub fn release_components(version: &str) -> Vec<u64> {
+    let version = version.strip_prefix(['v', 'V']).unwrap_or(version);
+    let len = version
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(version.len());
+    version[..len]
+        .split('.')
+        .map_while(|c| c.parse().ok())
+        .collect()
+}
+
+/// Determines how versions are parsed and ordered.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionScheme {
+    /// [Semantic Versioning](https://semver.org/)
+    #[default]
+    Semver,
+    /// [PEP 440](https://peps.python.org/pep-0440/) used by Python packages
+    Pep440,
+    /// Debian package versions as compared by dpkg
+    #[serde(alias = "dpkg")]
+    Debian,
+    /// Calendar versioning like `YYYY.MM.DD` or `YY.MM`
+    Calver,
+    /// Maven's `ComparableVersion`
+    Maven,
+    /// Dotted numeric versions with an optional suffix, e.g. `1.2` or `1.2-rc1`
+    Loose,
+}
+
+impl VersionScheme {
+    /// Compares two versions. Returns `None` if either of the versions cannot
+    /// be parsed according to the scheme.
+    pub fn compare(&self, a: &str, b: &str) -> Option<Ordering> {
+        match self {
+            VersionScheme::Semver => {
+                let a = semver::Version::parse(a).ok()?;
+                let b = semver::Version::parse(b).ok()?;
+                Some(a.cmp_precedence(&b))
+            }
+            VersionScheme::Pep440 => pep440::compare(a, b),
+            VersionScheme::Debian => debian::compare(a, b),
+            VersionScheme::Calver => loose::compare_calver(a, b),
+            VersionScheme::Maven => Some(maven::compare(a, b)),
+            VersionScheme::Loose => loose::compare(a, b),
+        }
+    }
+
+    /// The leading numeric release components of a version, skipping the
+    /// epoch of Debian and PEP 440 versions, e.g. `[2, 3]` for `1:2.3-1`.
+    pub fn release_components(&self, version: &str) -> Vec<u64> {
+        let epoch_separator = match self {
+            VersionScheme::Debian => Some(':'),
+            VersionScheme::Pep440 => Some('!'),
+            _ => None,
+        };
+        let version = epoch_separator
+            .and_then(|separator| version.split_once(separator))
+            .filter(|(epoch, _)| epoch.chars().all(|c| c.is_ascii_digit()))
+            .map_or(version, |(_, version)| version);
+        release_components(version)
+    }
+
+    /// Classifies the difference between two versions. Returns `None` if the
+    /// versions are identical or cannot be parsed according to the scheme.
+    pub fn severity(&self, a: &str, b: &str) -> Option<Severity> {
+        match self {
+            VersionScheme::Semver => {
+                let a = semver::Version::parse(a).ok()?;
+                let b = semver::Version::parse(b).ok()?;
+                if a.major != b.major {
+                    Some(Severity::Major)
+                } else if a.minor != b.minor {
+                    Some(Severity::Minor)
+                } else if a.patch != b.patch {
+                    Some(Severity::Patch)
+                } else if a.pre != b.pre {
+                    Some(Severity::Prerelease)
+                } else if a.build != b.build {
+                    Some(Severity::Build)
+                } else {
+                    None
+                }
+            }
+            VersionScheme::Pep440 => pep440::severity(a, b),
+            VersionScheme::Debian => debian::severity(a, b),
+            VersionScheme::Calver => loose::severity_calver(a, b),
+            VersionScheme::Maven => maven::severity(a, b),
+            VersionScheme::Loose => loose::severity(a, b),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::{Severity, VersionScheme};
+
+    #[test]
+    fn test_compare_semver() {
+        let scheme = VersionScheme::Semver;
+        assert_eq!(scheme.compare("1.2.3", "1.10.0"), Some(Ordering::Less));
+        assert_eq!(
+            scheme.compare("2.0.0", "2.0.0-rc.1"),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            scheme.compare("1.0.0+build.1", "1.0.0"),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(scheme.compare("1.2", "1.2.0"), None);
+    }
+
+    #[test]
+    fn test_severity_semver() {
+        let scheme = VersionScheme::Semver;
+        assert_eq!(scheme.severity("1.2.3", "2.0.0"), Some(Severity::Major));
+        assert_eq!(scheme.severity("1.2.3", "1.3.0"), Some(Severity::Minor));
+        assert_eq!(scheme.severity("1.2.3", "1.2.4"), Some(Severity::Patch));
+        assert_eq!(
+            scheme.severity("1.2.3-rc.1", "1.2.3"),
+            Some(Severity::Prerelease)
+        );
+        assert_eq!(scheme.severity("1.2.3+a", "1.2.3+b"), Some(Severity::Build));
+        assert_eq!(scheme.severity("1.2.3", "1.2.3"), None);
+        assert_eq!(scheme.severity("1.2", "1.2.3"), None);
+    }
+
+    #[test]
+    fn test_release_components_skip_epoch() {
+        assert_eq!(
+            VersionScheme::Semver.release_components("v1.12-rc1"),
+            [1, 12]
+        );
+        assert_eq!(VersionScheme::Debian.release_components("1:2.3-1"), [2, 3]);
+        assert_eq!(VersionScheme::Debian.release_components("2.3-1"), [2, 3]);
+        assert_eq!(
+            VersionScheme::Pep440.release_components("1!2.0.post1"),
+            [2, 0]
+        );
+        assert_eq!(VersionScheme::Loose.release_components("1:2.3"), [1]);
+    }
+
+    #[test]
+    fn test_deserialize_version_scheme() {
+        let schemes: Vec<VersionScheme> =
+            serde_yaml::from_str("[semver, pep440, debian, dpkg, calver, maven, loose]").unwrap();
+        assert_eq!(
+            schemes,
+            vec![
+                VersionScheme::Semver,
+                VersionScheme::Pep440,
+                VersionScheme::Debian,
+                VersionScheme::Debian,
+                VersionScheme::Calver,
+                VersionScheme::Maven,
+                VersionScheme::Loose,
+            ]
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use std::cmp::Ordering;
+
+use super::{release_severity, Severity};
+
+/// A Debian package version of the form `[epoch:]upstream_version[-revision]`.
+#[derive(Debug, PartialEq, Eq)]
+struct DebianVersion<'a> {
+    epoch: u64,
+    upstream: &'a str,
+    revision: &'a str,
+}
+
+fn parse(version: &str) -> Option<DebianVersion<'_>> {
+    let (epoch, rest) = match version.split_once(':') {
+        Some((epoch, rest)) => (epoch.parse().ok()?, rest),
+        None => (0, version),
+    };
+    let (upstream, revision) = rest.rsplit_once('-').unwrap_or((rest, ""));
+    if !upstream.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(DebianVersion {
+        epoch,
+        upstream,
+        revision,
+    })
+}
+
+/// Sort weight of a character in a non-digit part as defined by dpkg: `~`
+/// sorts before everything (even the end of the part), letters sort before
+/// non-letters.
+fn order(c: Option<u8>) -> i32 {
+    match c {
+        None => 0,
+        Some(c) if c.is_ascii_digit() => 0,
+        Some(c) if c.is_ascii_alphabetic() => c as i32,
+        Some(b'~') => -1,
+        Some(c) => c as i32 + 256,
+    }
+}
+
+/// Port of dpkg's `verrevcmp`.
+fn verrevcmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a.as_bytes(), b.as_bytes());
+    let is_digit = |s: &[u8]| s.first().is_some_and(u8::is_ascii_digit);
+    while !a.is_empty() || !b.is_empty() {
+        while (!a.is_empty() && !is_digit(a)) || (!b.is_empty() && !is_digit(b)) {
+            let (ac, bc) = (order(a.first().copied()), order(b.first().copied()));
+            if ac != bc {
+                return ac.cmp(&bc);
+            }
+            a = a.get(1..).unwrap_or_default();
+            b = b.get(1..).unwrap_or_default();
+        }
+        while a.first() == Some(&b'0') {
+            a = &a[1..];
+        }
+        while b.first() == Some(&b'0') {
+            b = &b[1..];
+        }
+        let mut first_diff = Ordering::Equal;
+        while is_digit(a) && is_digit(b) {
+            if first_diff.is_eq() {
+                first_diff = a[0].cmp(&b[0]);
+            }
+            a = &a[1..];
+            b = &b[1..];
+        }
+        if is_digit(a) {
+            return Ordering::Greater;
+        }
+        if is_digit(b) {
+            return Ordering::Less;
+        }
+        if first_diff.is_ne() {
+            return first_diff;
+        }
+    }
+    Ordering::Equal
+}
+
+fn leading_release(upstream: &str) -> Vec<u64> {
+    let len = upstream
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(upstream.len());
+    upstream[..len]
+        .split('.')
+        .map_while(|c| c.parse().ok())
+        .collect()
+}
+
+pub fn compare(a: &str, b: &str) -> Option<Ordering> {
+    let (a, b) = (parse(a)?, parse(b)?);
+    Some(
+        a.epoch
+            .cmp(&b.epoch)
+            .then_with(|| verrevcmp(a.upstream, b.upstream))
+            .then_with(|| verrevcmp(a.revision, b.revision)),
+    )
+}
+
+pub fn severity(a: &str, b: &str) -> Option<Severity> {
+    let (a, b) = (parse(a)?, parse(b)?);
+    if a.epoch != b.epoch {
+        return Some(Severity::Major);
+    }
+    release_severity(&leading_release(a.upstream), &leading_release(b.upstream))
+        .or_else(|| {
+            verrevcmp(a.upstream, b.upstream)
+                .is_ne()
+                .then_some(Severity::Prerelease)
+        })
+        .or_else(|| {
+            verrevcmp(a.revision, b.revision)
+                .is_ne()
+                .then_some(Severity::Build)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use crate::version_scheme::Severity;
+
+    use super::{compare, severity};
+
+    #[test]
+    fn test_compare_debian() {
+        assert_eq!(compare("1.2.3-1", "1.2.3-2"), Some(Ordering::Less));
+        assert_eq!(compare("1.2.3~rc1-1", "1.2.3-1"), Some(Ordering::Less));
+        assert_eq!(compare("1:1.0-1", "2.0-1"), Some(Ordering::Greater));
+        assert_eq!(compare("1.10", "1.9"), Some(Ordering::Greater));
+        assert_eq!(compare("1.0a", "1.0+"), Some(Ordering::Less));
+        assert_eq!(compare("1.01", "1.1"), Some(Ordering::Equal));
+        assert_eq!(compare("unstable", "1.0"), None);
+        assert_eq!(severity("1.2.3-1", "1.2.3-2"), Some(Severity::Build));
+        assert_eq!(severity("1.2.3~rc1", "1.2.3"), Some(Severity::Prerelease));
+        assert_eq!(severity("1.2.3-1", "1.3.0-1"), Some(Severity::Minor));
+    }
+}
@@ -0,0 +1,141 @@
+use std::cmp::Ordering;
+
+use super::{compare_release, release_severity, Severity};
+
+/// A dotted numeric release like `1.2.3` followed by an arbitrary suffix.
+#[derive(Debug, PartialEq, Eq)]
+struct LooseVersion<'a> {
+    release: Vec<u64>,
+    /// Number of digits of the first release component as written.
+    first_component_digits: usize,
+    suffix: &'a str,
+}
+
+fn parse(version: &str) -> Option<LooseVersion<'_>> {
+    let release_len = version
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(version.len());
+    let release = version[..release_len].trim_end_matches('.');
+    if release.is_empty() {
+        return None;
+    }
+    let components: Vec<&str> = release.split('.').collect();
+    Some(LooseVersion {
+        release: components
+            .iter()
+            .map(|c| c.parse().ok())
+            .collect::<Option<_>>()?,
+        first_component_digits: components[0].len(),
+        suffix: version[release.len()..].trim_start_matches(['.', '-', '_', '+', '~']),
+    })
+}
+
+/// Compares strings such that runs of digits are compared numerically.
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    fn chunks(s: &str) -> impl Iterator<Item = &str> {
+        let mut rest = s;
+        std::iter::from_fn(move || {
+            let first = rest.chars().next()?;
+            let len = rest
+                .find(|c: char| c.is_ascii_digit() != first.is_ascii_digit())
+                .unwrap_or(rest.len());
+            let (chunk, remainder) = rest.split_at(len);
+            rest = remainder;
+            Some(chunk)
+        })
+    }
+
+    let mut a_chunks = chunks(a);
+    let mut b_chunks = chunks(b);
+    loop {
+        let ordering = match (a_chunks.next(), b_chunks.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a), Some(b)) => match (a.parse::<u64>(), b.parse::<u64>()) {
+                (Ok(a), Ok(b)) => a.cmp(&b),
+                _ => a.cmp(b),
+            },
+        };
+        if ordering.is_ne() {
+            return ordering;
+        }
+    }
+}
+
+fn compare_parsed(a: &LooseVersion, b: &LooseVersion) -> Ordering {
+    compare_release(&a.release, &b.release).then_with(|| {
+        // A suffix marks a pre-release, e.g. `1.2-rc1` < `1.2`.
+        match (a.suffix.is_empty(), b.suffix.is_empty()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => natural_cmp(a.suffix, b.suffix),
+        }
+    })
+}
+
+fn severity_parsed(a: &LooseVersion, b: &LooseVersion) -> Option<Severity> {
+    release_severity(&a.release, &b.release)
+        .or_else(|| (a.suffix != b.suffix).then_some(Severity::Prerelease))
+}
+
+pub fn compare(a: &str, b: &str) -> Option<Ordering> {
+    Some(compare_parsed(&parse(a)?, &parse(b)?))
+}
+
+pub fn severity(a: &str, b: &str) -> Option<Severity> {
+    severity_parsed(&parse(a)?, &parse(b)?)
+}
+
+/// Parses a calendar version, requiring the first component to be a two or
+/// four digit year.
+fn parse_calver(version: &str) -> Option<LooseVersion<'_>> {
+    parse(version).filter(|v| matches!(v.first_component_digits, 2 | 4))
+}
+
+pub fn compare_calver(a: &str, b: &str) -> Option<Ordering> {
+    Some(compare_parsed(&parse_calver(a)?, &parse_calver(b)?))
+}
+
+pub fn severity_calver(a: &str, b: &str) -> Option<Severity> {
+    severity_parsed(&parse_calver(a)?, &parse_calver(b)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use crate::version_scheme::Severity;
+
+    use super::{compare, compare_calver, severity, severity_calver};
+
+    #[test]
+    fn test_compare_loose() {
+        assert_eq!(compare("1.2", "1.2.0"), Some(Ordering::Equal));
+        assert_eq!(compare("1.9", "1.10"), Some(Ordering::Less));
+        assert_eq!(compare("1.2-rc1", "1.2"), Some(Ordering::Less));
+        assert_eq!(compare("1.2-rc2", "1.2-rc10"), Some(Ordering::Less));
+        assert_eq!(compare("latest", "1.2"), None);
+        assert_eq!(severity("1.2", "1.2.1"), Some(Severity::Patch));
+        assert_eq!(severity("1.2-rc1", "1.2"), Some(Severity::Prerelease));
+    }
+
+    #[test]
+    fn test_compare_calver() {
+        assert_eq!(
+            compare_calver("2024.01.15", "2024.02.01"),
+            Some(Ordering::Less)
+        );
+        assert_eq!(compare_calver("24.04", "23.10"), Some(Ordering::Greater));
+        assert_eq!(compare_calver("1.2.3", "2024.01.15"), None);
+        assert_eq!(
+            severity_calver("2023.12.01", "2024.01.15"),
+            Some(Severity::Major)
+        );
+        assert_eq!(
+            severity_calver("2024.01.01", "2024.02.01"),
+            Some(Severity::Minor)
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use std::cmp::Ordering;
+
+use super::{release_severity, Severity};
+
+/// Well-known qualifiers in ascending order. The empty qualifier represents a
+/// release.
+const QUALIFIERS: [&str; 7] = ["alpha", "beta", "milestone", "rc", "snapshot", "", "sp"];
+const RELEASE_QUALIFIER_INDEX: usize = 5;
+
+/// An item of a version as parsed by Maven's `ComparableVersion`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Item {
+    Int(u128),
+    Str(String),
+    List(Vec<Item>),
+}
+
+/// Sort key of a string qualifier. Unknown qualifiers sort after all known
+/// ones in lexical order.
+fn qualifier_key(qualifier: &str) -> (usize, &str) {
+    match QUALIFIERS.iter().position(|q| *q == qualifier) {
+        Some(index) => (index, ""),
+        None => (QUALIFIERS.len(), qualifier),
+    }
+}
+
+impl Item {
+    fn string(value: &str, followed_by_digit: bool) -> Self {
+        let value = match value {
+            "a" if followed_by_digit => "alpha",
+            "b" if followed_by_digit => "beta",
+            "m" if followed_by_digit => "milestone",
+            "ga" | "final" | "release" => "",
+            "cr" => "rc",
+            value => value,
+        };
+        Item::Str(value.into())
+    }
+
+    fn parse(value: &str, is_digit: bool) -> Self {
+        if is_digit {
+            // Versions with numbers exceeding `u128` are effectively
+            // nonexistent, so saturating is fine.
+            Item::Int(value.parse().unwrap_or(u128::MAX))
+        } else {
+            Item::string(value, false)
+        }
+    }
+
+    fn is_null(&self) -> bool {
+        match self {
+            Item::Int(value) => *value == 0,
+            Item::Str(value) => qualifier_key(value).0 == RELEASE_QUALIFIER_INDEX,
+            Item::List(items) => items.is_empty(),
+        }
+    }
+
+    /// Compares to a missing item, e.g. `1.0` vs `1`.
+    fn cmp_null(&self) -> Ordering {
+        match self {
+            Item::Int(value) => value.cmp(&0),
+            Item::Str(value) => qualifier_key(value).cmp(&(RELEASE_QUALIFIER_INDEX, "")),
+            Item::List(items) => items.first().map_or(Ordering::Equal, Item::cmp_null),
+        }
+    }
+
+    fn cmp_item(&self, other: &Item) -> Ordering {
+        match (self, other) {
+            (Item::Int(a), Item::Int(b)) => a.cmp(b),
+            (Item::Int(_), _) => Ordering::Greater,
+            (Item::Str(_), Item::Int(_)) => Ordering::Less,
+            (Item::Str(a), Item::Str(b)) => qualifier_key(a).cmp(&qualifier_key(b)),
+            (Item::Str(_), Item::List(_)) => Ordering::Less,
+            (Item::List(_), Item::Int(_)) => Ordering::Less,
+            (Item::List(_), Item::Str(_)) => Ordering::Greater,
+            (Item::List(a), Item::List(b)) => {
+                for i in 0..a.len().max(b.len()) {
+                    let ordering = match (a.get(i), b.get(i)) {
+                        (None, None) => Ordering::Equal,
+                        (None, Some(b)) => b.cmp_null().reverse(),
+                        (Some(a), None) => a.cmp_null(),
+                        (Some(a), Some(b)) => a.cmp_item(b),
+                    };
+                    if ordering.is_ne() {
+                        return ordering;
+                    }
+                }
+                Ordering::Equal
+            }
+        }
+    }
+}
+
+/// Removes null items from the end of a list. Non-null nested lists are
+/// skipped, i.e. null items in front of them are removed as well.
+fn normalize(items: &mut Vec<Item>) {
+    for i in (0..items.len()).rev() {
+        if items[i].is_null() {
+            items.remove(i);
+        } else if !matches!(items[i], Item::List(_)) {
+            break;
+        }
+    }
+}
+
+/// Port of the parsing of Maven's `ComparableVersion`. Nested lists are built
+/// on a stack and attached to their parent once complete.
+fn parse(version: &str) -> Item {
+    let version = version.to_lowercase();
+    let mut stack: Vec<Vec<Item>> = vec![vec![]];
+    let mut is_digit = false;
+    let mut start = 0;
+
+    for (i, c) in version.char_indices() {
+        let list = stack.last_mut().unwrap();
+        match c {
+            '.' | '-' => {
+                if i == start {
+                    list.push(Item::Int(0));
+                } else {
+                    list.push(Item::parse(&version[start..i], is_digit));
+                }
+                is_digit = false;
+                start = i + 1;
+                if c == '-' {
+                    stack.push(vec![]);
+                }
+            }
+            c if c.is_ascii_digit() => {
+                if !is_digit && i > start {
+                    // `1.0.0.X1 < 1.0.0-X2`: treat `.X` as `-X` for any
+                    // string qualifier `X`.
+                    if !list.is_empty() {
+                        stack.push(vec![]);
+                    }
+                    stack
+                        .last_mut()
+                        .unwrap()
+                        .push(Item::string(&version[start..i], true));
+                    start = i;
+                    stack.push(vec![]);
+                }
+                is_digit = true;
+            }
+            _ => {
+                if is_digit && i > start {
+                    list.push(Item::parse(&version[start..i], true));
+                    start = i;
+                    stack.push(vec![]);
+                }
+                is_digit = false;
+            }
+        }
+    }
+    if version.len() > start {
+        if !is_digit && !stack.last().unwrap().is_empty() {
+            stack.push(vec![]);
+        }
+        stack
+            .last_mut()
+            .unwrap()
+            .push(Item::parse(&version[start..], is_digit));
+    }
+
+    let mut item = stack.pop().unwrap();
+    normalize(&mut item);
+    while let Some(mut parent) = stack.pop() {
+        parent.push(Item::List(item));
+        normalize(&mut parent);
+        item = parent;
+    }
+    Item::List(item)
+}
+
+pub fn compare(a: &str, b: &str) -> Ordering {
+    parse(a).cmp_item(&parse(b))
+}
+
+fn leading_release(item: &Item) -> Vec<u64> {
+    match item {
+        Item::List(items) => items
+            .iter()
+            .map_while(|item| match item {
+                Item::Int(value) => u64::try_from(*value).ok(),
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+pub fn severity(a: &str, b: &str) -> Option<Severity> {
+    let (a, b) = (parse(a), parse(b));
+    release_severity(&leading_release(&a), &leading_release(&b))
+        .or_else(|| a.cmp_item(&b).is_ne().then_some(Severity::Prerelease))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use crate::version_scheme::Severity;
+
+    use super::{compare, severity};
+
+    #[test]
+    fn test_compare_maven() {
+        let qualifiers = [
+            "1-alpha2snapshot",
+            "1-alpha2",
+            "1-alpha-123",
+            "1-beta-2",
+            "1-beta123",
+            "1-m2",
+            "1-m11",
+            "1-rc",
+            "1-cr2",
+            "1-rc123",
+            "1-SNAPSHOT",
+            "1",
+            "1-sp",
+            "1-sp2",
+            "1-sp123",
+            "1-abc",
+            "1-def",
+            "1-pom-1",
+            "1-1-snapshot",
+            "1-1",
+            "1-2",
+            "1-123",
+        ];
+        let numbers = [
+            "2.0", "2.0.a", "2-1", "2.0.2", "2.0.123", "2.1.0", "2.1-a", "2.1b", "2.1-c", "2.1-1",
+            "2.1.0.1", "2.2", "2.123", "11.a2", "11.a11", "11.b2", "11.b11", "11.m2", "11.m11",
+            "11", "11.a", "11b", "11c", "11m",
+        ];
+        for ordered in [&qualifiers[..], &numbers[..]] {
+            for pair in ordered.windows(2) {
+                assert_eq!(
+                    compare(pair[0], pair[1]),
+                    Ordering::Less,
+                    "{} < {}",
+                    pair[0],
+                    pair[1]
+                );
+            }
+        }
+        for (a, b) in [
+            ("1", "1.0.0"),
+            ("1-ga", "1"),
+            ("1.0-final", "1"),
+            ("1cr", "1rc"),
+        ] {
+            assert_eq!(compare(a, b), Ordering::Equal, "{a} == {b}");
+        }
+    }
+
+    #[test]
+    fn test_severity_maven() {
+        assert_eq!(severity("1.2.3", "2.0"), Some(Severity::Major));
+        assert_eq!(severity("1.2-SNAPSHOT", "1.2"), Some(Severity::Prerelease));
+        assert_eq!(severity("1.0", "1"), None);
+    }
+}
@@ -0,0 +1,182 @@
+use std::{cmp::Ordering, sync::OnceLock};
+
+use regex::Regex;
+
+use super::{compare_release, release_severity, Severity};
+
+fn pep440_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(
+            r"(?xi)^\s*v?
+            (?:(?P<epoch>[0-9]+)!)?
+            (?P<release>[0-9]+(?:\.[0-9]+)*)
+            (?:[-_.]?(?P<pre_l>alpha|a|beta|b|preview|pre|c|rc)[-_.]?(?P<pre_n>[0-9]+)?)?
+            (?:-(?P<post_n1>[0-9]+)|[-_.]?(?P<post_l>post|rev|r)[-_.]?(?P<post_n2>[0-9]+)?)?
+            (?:[-_.]?(?P<dev_l>dev)[-_.]?(?P<dev_n>[0-9]+)?)?
+            (?:\+(?P<local>[a-z0-9]+(?:[-_.][a-z0-9]+)*))?
+            \s*$",
+        )
+        .unwrap()
+    })
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum LocalSegment {
+    Alphanumeric(String),
+    Numeric(u64),
+}
+
+/// A parsed PEP 440 version.
+#[derive(Debug, PartialEq, Eq)]
+struct Pep440Version {
+    epoch: u64,
+    release: Vec<u64>,
+    /// Phase and number of the pre-release. Developmental releases without a
+    /// pre-release or post-release sort before any pre-release (phase `-1`),
+    /// final releases after all pre-releases (phase `3`).
+    pre: (i8, u64),
+    post: Option<u64>,
+    /// `(false, n)` for developmental releases, sorting before `(true, 0)`
+    /// for all other releases.
+    dev: (bool, u64),
+    local: Option<Vec<LocalSegment>>,
+}
+
+fn parse(version: &str) -> Option<Pep440Version> {
+    let captures = pep440_regex().captures(version)?;
+    let number = |name: &str| captures.name(name).map(|n| n.as_str().parse::<u64>().ok());
+
+    let pre_phase =
+        captures
+            .name("pre_l")
+            .map(|l| match l.as_str().to_ascii_lowercase().as_str() {
+                "a" | "alpha" => 0,
+                "b" | "beta" => 1,
+                _ => 2,
+            });
+    let pre_number = number("pre_n").unwrap_or(Some(0))?;
+    let post = match (number("post_n1"), captures.name("post_l")) {
+        (Some(n), _) => Some(n?),
+        (None, Some(_)) => Some(number("post_n2").unwrap_or(Some(0))?),
+        (None, None) => None,
+    };
+    let dev = match captures.name("dev_l") {
+        Some(_) => Some(number("dev_n").unwrap_or(Some(0))?),
+        None => None,
+    };
+
+    Some(Pep440Version {
+        epoch: number("epoch").unwrap_or(Some(0))?,
+        release: captures
+            .name("release")?
+            .as_str()
+            .split('.')
+            .map(|c| c.parse().ok())
+            .collect::<Option<_>>()?,
+        pre: match (pre_phase, post, dev) {
+            (Some(phase), _, _) => (phase, pre_number),
+            (None, None, Some(_)) => (-1, 0),
+            (None, _, _) => (3, 0),
+        },
+        post,
+        dev: match dev {
+            Some(n) => (false, n),
+            None => (true, 0),
+        },
+        local: captures.name("local").map(|local| {
+            local
+                .as_str()
+                .to_ascii_lowercase()
+                .split(['-', '_', '.'])
+                .map(|segment| match segment.parse() {
+                    Ok(n) => LocalSegment::Numeric(n),
+                    Err(_) => LocalSegment::Alphanumeric(segment.into()),
+                })
+                .collect()
+        }),
+    })
+}
+
+pub fn compare(a: &str, b: &str) -> Option<Ordering> {
+    let (a, b) = (parse(a)?, parse(b)?);
+    Some(
+        a.epoch
+            .cmp(&b.epoch)
+            .then_with(|| compare_release(&a.release, &b.release))
+            .then_with(|| a.pre.cmp(&b.pre))
+            .then_with(|| a.post.cmp(&b.post))
+            .then_with(|| a.dev.cmp(&b.dev))
+            .then_with(|| a.local.cmp(&b.local)),
+    )
+}
+
+pub fn severity(a: &str, b: &str) -> Option<Severity> {
+    let (a, b) = (parse(a)?, parse(b)?);
+    if a.epoch != b.epoch {
+        return Some(Severity::Major);
+    }
+    release_severity(&a.release, &b.release).or_else(|| {
+        if a.pre != b.pre || a.dev != b.dev {
+            Some(Severity::Prerelease)
+        } else if a.post != b.post || a.local != b.local {
+            Some(Severity::Build)
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use crate::version_scheme::Severity;
+
+    use super::{compare, severity};
+
+    #[test]
+    fn test_compare_pep440() {
+        let ordered = [
+            "1.0.dev456",
+            "1.0a1",
+            "1.0a2.dev456",
+            "1.0a12.dev456",
+            "1.0a12",
+            "1.0b1.dev456",
+            "1.0b2",
+            "1.0b2.post345.dev456",
+            "1.0b2.post345",
+            "1.0rc1.dev456",
+            "1.0rc1",
+            "1.0",
+            "1.0+abc.5",
+            "1.0+abc.7",
+            "1.0+5",
+            "1.0.post456.dev34",
+            "1.0.post456",
+            "1.1.dev1",
+        ];
+        for pair in ordered.windows(2) {
+            assert_eq!(
+                compare(pair[0], pair[1]),
+                Some(Ordering::Less),
+                "{} < {}",
+                pair[0],
+                pair[1]
+            );
+        }
+        assert_eq!(compare("1.0", "1.0.0"), Some(Ordering::Equal));
+        assert_eq!(compare("1.0-RC-1", "1.0rc1"), Some(Ordering::Equal));
+        assert_eq!(compare("1!0.1", "2.0"), Some(Ordering::Greater));
+        assert_eq!(compare("latest", "1.0"), None);
+    }
+
+    #[test]
+    fn test_severity_pep440() {
+        assert_eq!(severity("1.0", "2.0"), Some(Severity::Major));
+        assert_eq!(severity("1.0rc1", "1.0"), Some(Severity::Prerelease));
+        assert_eq!(severity("1.0", "1.0.post1"), Some(Severity::Build));
+        assert_eq!(severity("1.0", "1.0.0"), None);
+    }
+}